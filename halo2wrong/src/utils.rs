@@ -369,3 +369,24 @@ fn test_dimension_measurement() {
         }
     );
 }
+
+#[test]
+fn test_decompose() {
+    use crate::curves::{ff::Field, pasta::Fp};
+    use rand_core::OsRng;
+
+    for bit_len in [1, 4, 8, 16, 17, 64, 68] {
+        let number_of_limbs = (Fp::NUM_BITS as usize - 1) / bit_len + 1;
+        for _ in 0..100 {
+            let e = Fp::random(OsRng);
+            let limbs = decompose(e, number_of_limbs, bit_len);
+            assert_eq!(limbs.len(), number_of_limbs);
+
+            let limbs: Vec<big_uint> = limbs.into_iter().map(fe_to_big).collect();
+            for limb in limbs.iter() {
+                assert!(limb.bits() as usize <= bit_len);
+            }
+            assert_eq!(compose(limbs, bit_len), fe_to_big(e));
+        }
+    }
+}
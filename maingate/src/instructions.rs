@@ -354,7 +354,7 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
                     Term::unassigned_to_add(c),
                 ],
                 F::ZERO,
-                CombinationOptionCommon::OneLinerMul.into(),
+                CombinationOptionCommon::CombineToNextScaleMul(F::ZERO, F::ONE + F::ONE).into(),
             )?
             .swap_remove(2))
     }
@@ -400,7 +400,7 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
                 [
                     Term::assigned_to_mul(b),
                     Term::unassigned_to_mul(c),
-                    Term::assigned_to_add(a),
+                    Term::assigned_to_sub(a),
                 ],
                 F::ZERO,
                 CombinationOptionCommon::OneLinerMul.into(),
@@ -548,6 +548,17 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
         Ok(())
     }
 
+    /// Enforces two witnesses are equal with a single copy constraint, without
+    /// taking a row
+    fn assert_equal_by_copy(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<(), Error> {
+        ctx.constrain_equal(a.cell(), b.cell())
+    }
+
     /// Enforces two witness is not equal
     fn assert_not_equal(
         &self,
@@ -698,6 +709,17 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
             .swap_remove(1))
     }
 
+    /// Assigns a new witness `r` as:
+    /// `r = a - constant`
+    fn sub_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        constant: F,
+    ) -> Result<AssignedValue<F>, Error> {
+        self.add_constant(ctx, a, -constant)
+    }
+
     /// Assigns a new witness `r` as:
     /// `r = a - b`
     fn sub(
@@ -734,6 +756,16 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
             .swap_remove(2))
     }
 
+    /// Assigns a new witness `r` as:
+    /// `r = -a`
+    fn neg(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error> {
+        self.neg_with_constant(ctx, a, F::ZERO)
+    }
+
     /// Assigns a new witness `r` as:
     /// `r = -a + constant`
     fn neg_with_constant(
@@ -799,6 +831,26 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
             .swap_remove(3))
     }
 
+    /// Assigns a new witness `r` as:
+    /// `r = a * constant`
+    fn mul_by_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        constant: F,
+    ) -> Result<AssignedValue<F>, Error> {
+        let c = a.value().map(|a| *a * constant);
+
+        Ok(self
+            .apply(
+                ctx,
+                [Term::Assigned(a, constant), Term::unassigned_to_sub(c)],
+                F::ZERO,
+                CombinationOptionCommon::OneLinerAdd.into(),
+            )?
+            .swap_remove(1))
+    }
+
     /// Assigns a new witness `r` as:
     /// `r = a * b`
     fn mul(
@@ -878,8 +930,8 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
             .swap_remove(2))
     }
 
-    /// Assigns a new bit witness `r` to `0` if both given witneeses are not `0`
-    /// otherwise `1`
+    /// Enforces that given bits are not both `1`
+    /// `a * b = 0`
     fn nand(
         &self,
         ctx: &mut RegionCtx<'_, F>,
@@ -1199,6 +1251,19 @@ pub trait MainGateInstructions<F: PrimeField, const WIDTH: usize>: Chip<F> {
     /// Increments the offset with all zero selectors
     fn no_operation(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error>;
 
+    /// Assigns zero to every advice cell of the current row with all zero
+    /// selectors and increments the offset. Unlike [`Self::no_operation`] no
+    /// advice cell of the row is left unassigned
+    fn assign_zero_row(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error> {
+        self.apply(
+            ctx,
+            iter::empty(),
+            F::ZERO,
+            CombinationOptionCommon::OneLinerAdd.into(),
+        )?;
+        Ok(())
+    }
+
     /// Given specific option combines `WIDTH` sized terms and assigns new
     /// value.
     fn apply<'t>(
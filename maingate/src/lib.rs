@@ -11,6 +11,8 @@ use halo2wrong::halo2::circuit::AssignedCell;
 mod instructions;
 mod main_gate;
 mod range;
+#[cfg(test)]
+mod test_utils;
 
 pub use halo2wrong::{halo2, utils::*, RegionCtx};
 pub use instructions::{CombinationOptionCommon, MainGateInstructions, Term};
@@ -525,17 +525,21 @@ impl<F: PrimeField> MainGate<F> {
 #[cfg(test)]
 mod tests {
 
-    use super::{MainGate, MainGateConfig, Term};
-    use crate::curves::{ff::PrimeField, pasta::Fp};
+    use super::{CombinationOption, MainGate, MainGateConfig, Term};
+    use crate::curves::{
+        ff::{Field, PrimeField},
+        pasta::Fp,
+    };
     use crate::halo2::circuit::{Layouter, SimpleFloorPlanner, Value};
-    use crate::halo2::dev::MockProver;
+    use crate::halo2::dev::{MockProver, VerifyFailure};
     use crate::halo2::plonk::{Circuit, ConstraintSystem, Error};
     use crate::main_gate::{CombinationOptionCommon, MainGateInstructions};
-    use crate::AssignedCondition;
+    use crate::test_utils::{self, Configure, TestCircuitWith, TestConfig};
+    use crate::{AssignedCondition, RangeChip};
     use halo2wrong::utils::{big_to_fe, decompose};
     use halo2wrong::RegionCtx;
     use rand_core::OsRng;
-    use std::marker::PhantomData;
+    use std::{iter, marker::PhantomData};
 
     #[derive(Clone)]
     struct TestCircuitConfig {
@@ -551,6 +555,27 @@ mod tests {
         }
     }
 
+    impl<F: PrimeField> TestConfig<F> for TestCircuitConfig {}
+
+    impl<F: PrimeField> Configure<F> for TestCircuitConfig {
+        type Config = Self;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            TestCircuitConfig { main_gate_config }
+        }
+    }
+
+    fn verify<S>(synthesize: S) -> Result<(), Vec<VerifyFailure>>
+    where
+        S: Fn(&MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        const K: u32 = 8;
+        test_utils::verify::<TestCircuitConfig, _>(K, move |config, ctx| {
+            synthesize(&config.main_gate(), ctx)
+        })
+    }
+
     #[derive(Default)]
     struct TestCircuitPublicInputs<F: PrimeField> {
         _marker: PhantomData<F>,
@@ -609,6 +634,14 @@ mod tests {
             Err(e) => panic!("{:#?}", e),
         };
         assert_eq!(prover.verify(), Ok(()));
+
+        // Instance that doesn't match the exposed cell is rejected
+        let public_inputs = vec![vec![public_input + Fp::ONE]];
+        let prover = match MockProver::run(K, &circuit, public_inputs) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_ne!(prover.verify(), Ok(()));
     }
 
     #[derive(Default)]
@@ -1590,4 +1623,728 @@ mod tests {
         };
         assert_eq!(prover.verify(), Ok(()));
     }
+
+    #[test]
+    fn test_main_gate_without_witnesses() {
+        use crate::curves::pasta::EqAffine;
+        use crate::halo2::plonk::{keygen_pk, keygen_vk};
+        use crate::halo2::poly::commitment::ParamsProver;
+        use crate::halo2::poly::ipa::commitment::ParamsIPA;
+
+        const K: u32 = 8;
+        let circuit = TestCircuitWith::<Fp, TestCircuitConfig>::new(|config, ctx, witness| {
+            let main_gate = config.main_gate::<Fp>();
+            let a = main_gate.assign_value(ctx, witness.map(|()| Fp::from(3)))?;
+            main_gate.assert_equal_to_constant(ctx, &a, Fp::from(3))
+        });
+        let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Keys are generated without the witness, which mock prover can't run
+        let circuit = circuit.without_witnesses();
+        let params = ParamsIPA::<EqAffine>::new(K);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        keygen_pk(&params, vk, &circuit).unwrap();
+        assert!(matches!(
+            MockProver::run(K, &circuit, vec![vec![]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[test]
+    fn test_main_gate_add_chain() {
+        let values: Vec<Fp> = (0..4).map(|_| Fp::random(OsRng)).collect();
+
+        // Sums are carried into the next addition by copy constraints
+        for forged in [false, true] {
+            let values = values.clone();
+            let result = verify(move |main_gate, ctx| {
+                let assigned = values
+                    .iter()
+                    .map(|value| main_gate.assign_value(ctx, Value::known(*value)))
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                let sum = if forged {
+                    let sum = assigned[0].value().zip(assigned[1].value());
+                    let sum = sum.map(|(a, b)| *a + b + Fp::ONE);
+                    main_gate
+                        .apply(
+                            ctx,
+                            [
+                                Term::assigned_to_add(&assigned[0]),
+                                Term::assigned_to_add(&assigned[1]),
+                                Term::unassigned_to_sub(sum),
+                            ],
+                            Fp::ZERO,
+                            CombinationOptionCommon::OneLinerAdd.into(),
+                        )?
+                        .swap_remove(2)
+                } else {
+                    main_gate.add(ctx, &assigned[0], &assigned[1])?
+                };
+                let sum = main_gate.add(ctx, &sum, &assigned[2])?;
+                let sum = main_gate.add(ctx, &sum, &assigned[3])?;
+
+                let expected = values.iter().fold(Fp::ZERO, |acc, value| acc + value);
+                main_gate.assert_equal_to_constant(ctx, &sum, expected + Fp::from(forged as u64))
+            });
+            assert_eq!(result.is_ok(), !forged, "forged: {forged}");
+        }
+    }
+
+    #[test]
+    fn test_main_gate_sub_and_neg() {
+        // Results below zero wrap around the modulus
+        let one = Fp::ONE;
+        for (a, b) in [
+            (one, one + one),
+            (Fp::ZERO, one),
+            (Fp::random(OsRng), Fp::random(OsRng)),
+        ] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+
+                let dif = &main_gate.sub(ctx, assigned_a, assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, dif, a - b)?;
+                let neg = &main_gate.neg(ctx, assigned_a)?;
+                main_gate.assert_equal_to_constant(ctx, neg, -a)?;
+                let sum = main_gate.add(ctx, assigned_a, neg)?;
+                main_gate.assert_zero(ctx, &sum)?;
+
+                // `a - b` is `a + (-b)`
+                let neg = &main_gate.neg(ctx, assigned_b)?;
+                let sum = main_gate.add(ctx, assigned_a, neg)?;
+                main_gate.assert_equal(ctx, dif, &sum)
+            });
+            assert_eq!(result, Ok(()));
+        }
+
+        // Negation of zero is zero
+        let result = verify(|main_gate, ctx| {
+            let zero = &main_gate.assign_constant(ctx, Fp::ZERO)?;
+            let neg = &main_gate.neg(ctx, zero)?;
+            main_gate.assert_zero(ctx, neg)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Difference that doesn't wrap around is rejected
+        let result = verify(|main_gate, ctx| {
+            let a = &main_gate.assign_value(ctx, Value::known(Fp::ONE))?;
+            let b = &main_gate.assign_value(ctx, Value::known(Fp::from(2)))?;
+            let dif = Value::known(Fp::from(1u64 << 63));
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_add(a),
+                    Term::assigned_to_sub(b),
+                    Term::unassigned_to_sub(dif),
+                ],
+                Fp::ZERO,
+                CombinationOptionCommon::OneLinerAdd.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_mul_and_mul_add() {
+        for _ in 0..10 {
+            let (a, b, c) = (Fp::random(OsRng), Fp::random(OsRng), Fp::random(OsRng));
+            let result = verify(move |main_gate, ctx| {
+                let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                let assigned_c = &main_gate.assign_value(ctx, Value::known(c))?;
+
+                let ab = &main_gate.mul(ctx, assigned_a, assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, ab, a * b)?;
+                let abc = &main_gate.mul_add(ctx, assigned_a, assigned_b, assigned_c)?;
+                main_gate.assert_equal_to_constant(ctx, abc, a * b + c)?;
+
+                // Operands from earlier rows: `(a * b) * c + a * b`
+                let r = main_gate.mul_add(ctx, ab, assigned_c, ab)?;
+                main_gate.assert_equal_to_constant(ctx, &r, a * b * c + a * b)
+            });
+            assert_eq!(result, Ok(()));
+        }
+
+        // Forged product is rejected
+        let result = verify(|main_gate, ctx| {
+            let a = &main_gate.assign_value(ctx, Value::known(Fp::from(3)))?;
+            let b = &main_gate.assign_value(ctx, Value::known(Fp::from(5)))?;
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(a),
+                    Term::assigned_to_mul(b),
+                    Term::unassigned_to_sub(Value::known(Fp::from(16))),
+                ],
+                Fp::ZERO,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_assert_equal() {
+        let value = Fp::random(OsRng);
+        for (other, expect_ok) in [(value, true), (value + Fp::ONE, false)] {
+            // With a row of the main gate
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(value))?;
+                let b = &main_gate.assign_value(ctx, Value::known(other))?;
+                main_gate.assert_equal(ctx, a, b)
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+
+            // With the permutation argument only
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(value))?;
+                let b = &main_gate.assign_value(ctx, Value::known(other))?;
+                main_gate.assert_equal_by_copy(ctx, a, b)
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(other))?;
+                main_gate.assert_equal_to_constant(ctx, a, value)
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
+    }
+
+    #[test]
+    fn test_main_gate_assert_zero_one_and_bit() {
+        let zero = Fp::ZERO;
+        let one = Fp::ONE;
+        let two = Fp::from(2);
+
+        for (value, zero_ok, one_ok, bit_ok) in [
+            (zero, true, false, true),
+            (one, false, true, true),
+            (two, false, false, false),
+            (-one, false, false, false),
+        ] {
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(value))?;
+                main_gate.assert_zero(ctx, a)
+            });
+            assert_eq!(result.is_ok(), zero_ok, "assert_zero: {value:?}");
+
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(value))?;
+                main_gate.assert_one(ctx, a)
+            });
+            assert_eq!(result.is_ok(), one_ok, "assert_one: {value:?}");
+
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_value(ctx, Value::known(value))?;
+                main_gate.assert_bit(ctx, a)
+            });
+            assert_eq!(result.is_ok(), bit_ok, "assert_bit: {value:?}");
+        }
+    }
+
+    #[test]
+    fn test_main_gate_select_needs_bit_condition() {
+        let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+        for (cond, expected) in [(Fp::ONE, a), (Fp::ZERO, b)] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                let cond = &main_gate.assign_bit(ctx, Value::known(cond))?;
+                let selected = &main_gate.select(ctx, assigned_a, assigned_b, cond)?;
+                main_gate.assert_equal_to_constant(ctx, selected, expected)?;
+                let selected = &main_gate.select_or_assign(ctx, assigned_a, b, cond)?;
+                main_gate.assert_equal_to_constant(ctx, selected, expected)
+            });
+            assert_eq!(result, Ok(()));
+        }
+
+        // Selection row alone is satisfied by `cond = 2` and `res = 2a - b`,
+        // it is the bitness of the condition that rules such values out
+        for assert_bit in [false, true] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                let cond = &main_gate.assign_value(ctx, Value::known(Fp::from(2)))?;
+                if assert_bit {
+                    main_gate.assert_bit(ctx, cond)?;
+                }
+                let res = Value::known(a + a - b);
+                let assigned = main_gate.apply(
+                    ctx,
+                    [
+                        Term::assigned_to_mul(cond),
+                        Term::assigned_to_mul(assigned_a),
+                        Term::assigned_to_mul(cond),
+                        Term::assigned_to_add(assigned_b),
+                        Term::unassigned_to_sub(res),
+                    ],
+                    Fp::ZERO,
+                    CombinationOption::OneLinerDoubleMul(-Fp::ONE),
+                )?;
+                ctx.constrain_equal(assigned[0].cell(), assigned[2].cell())
+            });
+            assert_eq!(result.is_ok(), !assert_bit);
+        }
+    }
+
+    #[test]
+    fn test_main_gate_invert() {
+        // Zero is flagged as non invertible
+        let result = verify(|main_gate, ctx| {
+            let zero = &main_gate.assign_value(ctx, Value::known(Fp::ZERO))?;
+            let (_, is_zero) = main_gate.invert(ctx, zero)?;
+            main_gate.assert_one(ctx, &is_zero)
+        });
+        assert_eq!(result, Ok(()));
+
+        let a = Fp::random(OsRng);
+        let result = verify(move |main_gate, ctx| {
+            let assigned = &main_gate.assign_value(ctx, Value::known(a))?;
+            let (inverse, is_zero) = main_gate.invert(ctx, assigned)?;
+            main_gate.assert_zero(ctx, &is_zero)?;
+            main_gate.assert_equal_to_constant(ctx, &inverse, a.invert().unwrap())?;
+            let inverse = main_gate.invert_unsafe(ctx, assigned)?;
+            main_gate.assert_equal_to_constant(ctx, &inverse, a.invert().unwrap())
+        });
+        assert_eq!(result, Ok(()));
+
+        // Forged inverse is rejected
+        let result = verify(move |main_gate, ctx| {
+            let assigned = &main_gate.assign_value(ctx, Value::known(a))?;
+            let inverse = Value::known(a.invert().unwrap() + Fp::ONE);
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(assigned),
+                    Term::unassigned_to_mul(inverse),
+                ],
+                -Fp::ONE,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        // Non zero value can't be flagged as non invertible, `a' = 1` satisfies
+        // `r * a' - r = 0` but not `a * a' - 1 + r = 0`
+        let result = verify(move |main_gate, ctx| {
+            let assigned = &main_gate.assign_value(ctx, Value::known(a))?;
+            let is_zero = &main_gate.assign_bit(ctx, Value::known(Fp::ONE))?;
+            let inverse = main_gate
+                .apply(
+                    ctx,
+                    [
+                        Term::assigned_to_mul(assigned),
+                        Term::unassigned_to_mul(Value::known(Fp::ONE)),
+                        Term::assigned_to_add(is_zero),
+                    ],
+                    -Fp::ONE,
+                    CombinationOptionCommon::OneLinerMul.into(),
+                )?
+                .swap_remove(1);
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(is_zero),
+                    Term::assigned_to_mul(&inverse),
+                    Term::assigned_to_sub(is_zero),
+                ],
+                Fp::ZERO,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_is_zero_and_is_equal() {
+        // Witness layout of `is_equal` with given flag `r` and hint `x`
+        fn is_equal_with(
+            main_gate: &MainGate<Fp>,
+            ctx: &mut RegionCtx<'_, Fp>,
+            a: Fp,
+            b: Fp,
+            r: Fp,
+            x: Fp,
+        ) -> Result<(), Error> {
+            let a = &main_gate.assign_value(ctx, Value::known(a))?;
+            let b = &main_gate.assign_value(ctx, Value::known(b))?;
+            let r = &main_gate.assign_bit(ctx, Value::known(r))?;
+            let dif = &main_gate.sub(ctx, a, b)?;
+            let u = r.value().map(|r| *r - *r * x + x);
+            let u = &main_gate
+                .apply(
+                    ctx,
+                    [
+                        Term::assigned_to_sub(r),
+                        Term::unassigned_to_sub(Value::known(x)),
+                        Term::unassigned_to_add(u),
+                    ],
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerMul.into(),
+                )?
+                .swap_remove(2);
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(dif),
+                    Term::assigned_to_mul(u),
+                    Term::assigned_to_add(r),
+                ],
+                -Fp::ONE,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        }
+
+        for _ in 0..10 {
+            let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+            for (a, b, equal) in [(a, b, false), (a, a, true)] {
+                let result = verify(move |main_gate, ctx| {
+                    let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                    let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                    let is_equal = &main_gate.is_equal(ctx, assigned_a, assigned_b)?;
+                    main_gate.assert_equal_to_constant(ctx, is_equal, Fp::from(equal as u64))?;
+
+                    let dif = &main_gate.sub(ctx, assigned_a, assigned_b)?;
+                    let is_zero = &main_gate.is_zero(ctx, dif)?;
+                    main_gate.assert_equal(ctx, is_equal, is_zero)
+                });
+                assert_eq!(result, Ok(()));
+
+                let x = (a - b).invert().unwrap_or(Fp::ONE);
+                let r = Fp::from(equal as u64);
+                let result =
+                    verify(move |main_gate, ctx| is_equal_with(main_gate, ctx, a, b, r, x));
+                assert_eq!(result, Ok(()));
+
+                // Flag can't be claimed the other way around whatever the hint is
+                let r = Fp::from(!equal as u64);
+                for x in [Fp::ZERO, Fp::ONE, Fp::random(OsRng)] {
+                    let result =
+                        verify(move |main_gate, ctx| is_equal_with(main_gate, ctx, a, b, r, x));
+                    assert!(result.is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_main_gate_boolean_logic() {
+        for (a, b) in [(0u64, 0u64), (0, 1), (1, 0), (1, 1)] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned_a = &main_gate.assign_bit(ctx, Value::known(Fp::from(a)))?;
+                let assigned_b = &main_gate.assign_bit(ctx, Value::known(Fp::from(b)))?;
+
+                let and = &main_gate.and(ctx, assigned_a, assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, and, Fp::from(a & b))?;
+                let or = &main_gate.or(ctx, assigned_a, assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, or, Fp::from(a | b))?;
+                let xor = &main_gate.xor(ctx, assigned_a, assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, xor, Fp::from(a ^ b))?;
+                let not = &main_gate.not(ctx, assigned_a)?;
+                main_gate.assert_equal_to_constant(ctx, not, Fp::from(1 - a))
+            });
+            assert_eq!(result, Ok(()), "a: {a}, b: {b}");
+
+            let result = verify(move |main_gate, ctx| {
+                let a = &main_gate.assign_bit(ctx, Value::known(Fp::from(a)))?;
+                let b = &main_gate.assign_bit(ctx, Value::known(Fp::from(b)))?;
+                main_gate.nand(ctx, a, b)
+            });
+            assert_eq!(result.is_ok(), a & b == 0, "a: {a}, b: {b}");
+        }
+
+        // Forged `1 && 1 = 0` is rejected
+        let result = verify(|main_gate, ctx| {
+            let one = &main_gate.assign_bit(ctx, Value::known(Fp::ONE))?;
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(one),
+                    Term::assigned_to_mul(one),
+                    Term::unassigned_to_sub(Value::known(Fp::ZERO)),
+                ],
+                Fp::ZERO,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_assign_constant() {
+        let constant = Fp::random(OsRng);
+
+        // Assigned constant takes part in copy constraints as any other cell
+        let result = verify(move |main_gate, ctx| {
+            let assigned = &main_gate.assign_constant(ctx, constant)?;
+            let value = &main_gate.assign_value(ctx, Value::known(constant))?;
+            main_gate.assert_equal_by_copy(ctx, assigned, value)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Advice cell of the constant can't hold another value
+        let result = verify(move |main_gate, ctx| {
+            main_gate.apply(
+                ctx,
+                [Term::unassigned_to_sub(Value::known(constant + Fp::ONE))],
+                constant,
+                CombinationOptionCommon::OneLinerAdd.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_apply_term_variants() {
+        let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+        for forged in [false, true] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                // `2a + 3b - c = 0` with unused cells in between
+                let c = Fp::from(2) * a + Fp::from(3) * b + Fp::from(forged as u64);
+                let assigned = main_gate.apply(
+                    ctx,
+                    [
+                        Term::Unassigned(Value::known(a), Fp::from(2)),
+                        Term::Zero,
+                        Term::Assigned(assigned_b, Fp::from(3)),
+                        Term::Zero,
+                        Term::unassigned_to_sub(Value::known(c)),
+                    ],
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+                assert_eq!(assigned.len(), 5);
+
+                // Unused cells are assigned to zero
+                main_gate.assert_zero(ctx, &assigned[1])?;
+                main_gate.assert_zero(ctx, &assigned[3])
+            });
+            assert_eq!(result.is_ok(), !forged);
+        }
+    }
+
+    #[test]
+    fn test_main_gate_compose_long_sum() {
+        let values: Vec<Fp> = (0..12).map(|_| Fp::random(OsRng)).collect();
+        let bases: Vec<Fp> = (0..12).map(|_| Fp::random(OsRng)).collect();
+        let expected = values
+            .iter()
+            .zip(bases.iter())
+            .fold(Fp::ZERO, |acc, (value, base)| acc + *value * base);
+
+        let result = verify({
+            let values = values.clone();
+            move |main_gate, ctx| {
+                let assigned = values
+                    .iter()
+                    .map(|value| main_gate.assign_value(ctx, Value::known(*value)))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let terms: Vec<Term<Fp>> = assigned
+                    .iter()
+                    .zip(bases.iter())
+                    .map(|(assigned, base)| Term::Assigned(assigned, *base))
+                    .collect();
+                let composed = main_gate.compose(ctx, &terms, Fp::ZERO)?;
+                main_gate.assert_equal_to_constant(ctx, &composed, expected)
+            }
+        });
+        assert_eq!(result, Ok(()));
+
+        // Sum is carried to the next row in `e`, it can't be tampered with
+        for delta in [Fp::ZERO, Fp::ONE] {
+            let values = values.clone();
+            let result = verify(move |main_gate, ctx| {
+                let assigned = values[..8]
+                    .iter()
+                    .map(|value| main_gate.assign_value(ctx, Value::known(*value)))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let sum: Fp = values[..8].iter().sum();
+                let first: Fp = values[..4].iter().sum();
+
+                // `a_0 + a_1 + a_2 + a_3 - sum + e_next = 0`
+                main_gate.apply(
+                    ctx,
+                    assigned[..4]
+                        .iter()
+                        .map(Term::assigned_to_add)
+                        .chain(iter::once(Term::unassigned_to_sub(Value::known(sum)))),
+                    Fp::ZERO,
+                    CombinationOptionCommon::CombineToNextAdd(Fp::ONE).into(),
+                )?;
+                // `a_4 + a_5 + a_6 + a_7 - e = 0`
+                let remaining = Value::known(sum - first + delta);
+                main_gate.apply(
+                    ctx,
+                    assigned[4..]
+                        .iter()
+                        .map(Term::assigned_to_add)
+                        .chain(iter::once(Term::unassigned_to_sub(remaining))),
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), delta == Fp::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_main_gate_div_and_assert_not_zero() {
+        let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+        let expected = a * b.invert().unwrap();
+        let result = verify(move |main_gate, ctx| {
+            let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+            let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+            main_gate.assert_not_zero(ctx, assigned_b)?;
+
+            let (quotient, is_zero) = main_gate.div(ctx, assigned_a, assigned_b)?;
+            main_gate.assert_zero(ctx, &is_zero)?;
+            main_gate.assert_equal_to_constant(ctx, &quotient, expected)?;
+            let quotient = main_gate.div_unsafe(ctx, assigned_a, assigned_b)?;
+            main_gate.assert_equal_to_constant(ctx, &quotient, expected)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Division by zero is flagged by `div`
+        let result = verify(move |main_gate, ctx| {
+            let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+            let zero = &main_gate.assign_value(ctx, Value::known(Fp::ZERO))?;
+            let (_, is_zero) = main_gate.div(ctx, assigned_a, zero)?;
+            main_gate.assert_one(ctx, &is_zero)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Forged quotient is rejected, `b * q - a = 0`
+        let result = verify(move |main_gate, ctx| {
+            let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+            let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+            main_gate.apply(
+                ctx,
+                [
+                    Term::assigned_to_mul(assigned_b),
+                    Term::unassigned_to_mul(Value::known(-expected)),
+                    Term::assigned_to_sub(assigned_a),
+                ],
+                Fp::ZERO,
+                CombinationOptionCommon::OneLinerMul.into(),
+            )?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        let result = verify(|main_gate, ctx| {
+            let zero = &main_gate.assign_value(ctx, Value::known(Fp::ZERO))?;
+            main_gate.assert_not_zero(ctx, zero)
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_main_gate_constant_operands() {
+        let (a, k) = (Fp::random(OsRng), Fp::random(OsRng));
+        let result = verify(move |main_gate, ctx| {
+            let assigned = &main_gate.assign_value(ctx, Value::known(a))?;
+
+            let r = &main_gate.add_constant(ctx, assigned, k)?;
+            main_gate.assert_equal_to_constant(ctx, r, a + k)?;
+            let r = &main_gate.sub_constant(ctx, assigned, k)?;
+            main_gate.assert_equal_to_constant(ctx, r, a - k)?;
+            let r = &main_gate.neg_with_constant(ctx, assigned, k)?;
+            main_gate.assert_equal_to_constant(ctx, r, k - a)?;
+            let r = &main_gate.mul_by_constant(ctx, assigned, k)?;
+            main_gate.assert_equal_to_constant(ctx, r, a * k)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Constants are fixed, so `a * other` in the `a * k` row and
+        // `a + other` in the `a + k` row are rejected
+        let other = k + Fp::ONE;
+        for (base, constant, r) in [(k, Fp::ZERO, a * other), (Fp::ONE, k, a + other)] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned = &main_gate.assign_value(ctx, Value::known(a))?;
+                main_gate.apply(
+                    ctx,
+                    [
+                        Term::Assigned(assigned, base),
+                        Term::unassigned_to_sub(Value::known(r)),
+                    ],
+                    constant,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+                Ok(())
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_main_gate_to_bits_cost() {
+        const NUMBER_OF_BITS: usize = 64;
+        // A row per bit, four weighted bits per composition row and the final
+        // equality row
+        const ROWS: usize = NUMBER_OF_BITS + NUMBER_OF_BITS / 4 + 1;
+
+        let value = Fp::from(u64::MAX - 1);
+        for (value, expect_ok) in [(value, true), (value + value, false)] {
+            let result = verify(move |main_gate, ctx| {
+                let assigned = main_gate.assign_value(ctx, Value::known(value))?;
+                let offset = ctx.offset();
+                main_gate.to_bits(ctx, &assigned, NUMBER_OF_BITS)?;
+                assert_eq!(ctx.offset() - offset, ROWS);
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
+
+        // Same bits with the range chip take two rows of four 8 bit limbs but
+        // a table of `1 + 2^8` rows
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let main_gate_config = MainGate::<Fp>::configure(&mut meta);
+        let range_config =
+            RangeChip::<Fp>::configure(&mut meta, &main_gate_config, vec![8], vec![]);
+        let cost = range_config.cost(1, 8, NUMBER_OF_BITS);
+        assert_eq!(cost.rows, 2);
+        assert_eq!(cost.table_rows, 1 + (1 << 8));
+        assert!(cost.rows + cost.table_rows > ROWS);
+    }
+
+    #[test]
+    fn test_main_gate_padding_rows() {
+        let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+        for pad in [false, true] {
+            for (c, expect_ok) in [(a * b + a, true), (a * b, false)] {
+                let result = verify(move |main_gate, ctx| {
+                    let assigned_a = &main_gate.assign_value(ctx, Value::known(a))?;
+                    if pad {
+                        main_gate.no_operation(ctx)?;
+                        main_gate.assign_zero_row(ctx)?;
+                    }
+                    let assigned_b = &main_gate.assign_value(ctx, Value::known(b))?;
+                    let ab = &main_gate.mul(ctx, assigned_a, assigned_b)?;
+                    if pad {
+                        main_gate.assign_zero_row(ctx)?;
+                        main_gate.no_operation(ctx)?;
+                    }
+                    let r = &main_gate.add(ctx, ab, assigned_a)?;
+                    main_gate.assert_equal_to_constant(ctx, r, c)
+                });
+                assert_eq!(result.is_ok(), expect_ok, "pad: {pad}");
+            }
+        }
+    }
 }
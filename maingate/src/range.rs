@@ -1,36 +1,375 @@
-use super::main_gate::{MainGate, MainGateConfig};
+use super::main_gate::{CombinationOption, MainGate, MainGateConfig};
 use crate::halo2::circuit::Chip;
 use crate::halo2::circuit::Layouter;
 use crate::halo2::circuit::Value;
 use crate::halo2::halo2curves::ff::PrimeField;
-use crate::halo2::plonk::{ConstraintSystem, Error, Expression};
+use crate::halo2::plonk::{ConstraintSystem, Error, Expression, VirtualCells};
 use crate::halo2::plonk::{Selector, TableColumn};
 use crate::halo2::poly::Rotation;
-use crate::instructions::{MainGateInstructions, Term};
-use crate::AssignedValue;
+use crate::instructions::{CombinationOptionCommon, MainGateInstructions, Term};
+use crate::{AssignedCondition, AssignedValue};
 use halo2wrong::halo2::plonk::Advice;
 use halo2wrong::halo2::plonk::Column;
 use halo2wrong::halo2::plonk::Fixed;
-use halo2wrong::utils::decompose;
+use halo2wrong::utils::{big_to_fe, decompose, decompose_big, fe_to_big, modulus, power_of_two};
 use halo2wrong::RegionCtx;
+use num_bigint::BigUint;
 use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Maximum number of cells in one line enabled with composition selector
 pub const NUMBER_OF_LOOKUP_LIMBS: usize = 4;
 
+/// Number of table rows that are generated at once before they are assigned
+const TABLE_CHUNK_LEN: usize = 1 << 12;
+
 /// Range gate configuration
 #[derive(Clone, Debug)]
 pub struct RangeConfig {
     main_gate_config: MainGateConfig,
     bit_len_tag: BTreeMap<usize, usize>,
+    composition_bit_lens: BTreeSet<usize>,
+    overflow_bit_lens: BTreeSet<usize>,
     t_tag: TableColumn,
     t_value: TableColumn,
-    s_composition: Selector,
+    s_composition: LookupEnable,
     tag_composition: Option<Column<Fixed>>,
-    s_overflow: Option<Selector>,
+    s_overflow: Option<LookupEnable>,
     tag_overflow: Option<Column<Fixed>>,
+    bound_tag: BTreeMap<u64, usize>,
+    set: Option<(usize, Vec<BigUint>)>,
+    bitwise: Option<(usize, usize)>,
+    s_bitwise: Option<Selector>,
+    tag_bitwise: Option<Column<Fixed>>,
+    s_dynamic: Option<(Selector, Selector)>,
+    s_membership: Option<Selector>,
+    tag_membership: Option<Column<Fixed>>,
+    owns_table: bool,
+    blinding_factors: usize,
+    number_of_lookup_limbs: usize,
+    packed: bool,
+    wide: Option<WideConfig>,
+}
+
+/// Extra limb columns and composition gate selectors of the wide layout
+#[derive(Clone, Debug)]
+struct WideConfig {
+    advices: [Column<Advice>; NUMBER_OF_LOOKUP_LIMBS],
+    s_combine: Selector,
+    s_last: [Selector; 2 * NUMBER_OF_LOOKUP_LIMBS],
+    limb_bit_len: usize,
+}
+
+/// Enables a lookup argument at the rows it is assigned to. A complex
+/// selector is the default, a fixed column that the chip assigns `0` or `1`
+/// costs a fixed column but leaves selector combining to other gates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LookupEnable {
+    /// Complex selector
+    Selector(Selector),
+    /// Fixed column that is assigned `1` where the lookup is enabled
+    Fixed(Column<Fixed>),
+}
+
+impl From<Selector> for LookupEnable {
+    fn from(selector: Selector) -> Self {
+        Self::Selector(selector)
+    }
+}
+
+impl LookupEnable {
+    fn configure<F: PrimeField>(meta: &mut ConstraintSystem<F>, fixed: bool) -> Self {
+        if fixed {
+            Self::Fixed(meta.fixed_column())
+        } else {
+            Self::Selector(meta.complex_selector())
+        }
+    }
+
+    fn query<F: PrimeField>(&self, meta: &mut VirtualCells<'_, F>) -> Expression<F> {
+        match *self {
+            Self::Selector(selector) => meta.query_selector(selector),
+            Self::Fixed(column) => meta.query_fixed(column, Rotation::cur()),
+        }
+    }
+
+    /// Enables the lookup at the current row of the region
+    pub fn enable<F: PrimeField>(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error> {
+        match *self {
+            Self::Selector(selector) => ctx.enable(selector),
+            Self::Fixed(column) => ctx
+                .assign_fixed(|| "lookup enable", column, F::ONE)
+                .map(|_| ()),
+        }
+    }
+}
+
+/// Circuit resources that a number of decompositions take
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RangeCost {
+    /// Rows of the region
+    pub rows: usize,
+    /// Lookup arguments in the constraint system
+    pub lookup_arguments: usize,
+    /// Advice columns that carry limbs into lookups
+    pub lookup_advices: usize,
+    /// Rows of the lookup table
+    pub table_rows: usize,
+}
+
+/// Order of limbs that a decomposition returns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant limb first
+    #[default]
+    LittleEndian,
+    /// Most significant limb first
+    BigEndian,
+}
+
+/// Misuse of [`RangeChip`] that is detected at configuration or synthesis
+/// time. Instructions
+/// surface it as [`Error::Synthesis`] since [`Error`] can't carry it, or as
+/// [`Error::NotEnoughRowsAvailable`] if the table is too large.
+/// [`RangeChip::try_configure`], [`RangeChip::check_bit_len`],
+/// [`RangeChip::check_loaded`] and [`RangeConfig::check_k`] return it for
+/// inspection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeError {
+    /// Known value of `bits` bits doesn't fit in `bit_len` bits. Offset is the
+    /// row of the region where the value was going to be assigned
+    ValueTooLarge {
+        /// Bit length of the value
+        bits: usize,
+        /// Bit length that the value is ranged to
+        bit_len: usize,
+        /// Offset in the region
+        offset: usize,
+    },
+    /// Table of the owning config is not loaded by the chip
+    TableNotLoaded,
+    /// Layouter rejected the table assignment for a reason other than rows
+    TableAssignment,
+    /// Same advice column is given for more than one lookup, which would
+    /// leave a limb unchecked
+    DuplicateColumn {
+        /// Index of the advice column
+        index: usize,
+    },
+    /// Lookup table doesn't fit in the usable rows of a circuit of size `2^k`
+    /// or table assignment ran out of rows
+    TableTooLarge {
+        /// Size of the circuit
+        k: u32,
+        /// Smallest size that fits the table
+        required_k: u32,
+        /// Largest bit length in the table
+        bit_len: usize,
+        /// Rows of the table
+        table_len: usize,
+    },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::ValueTooLarge {
+                bits,
+                bit_len,
+                offset,
+            } => write!(
+                f,
+                "value of {bits} bits doesn't fit in {bit_len} bits at offset {offset}"
+            ),
+            RangeError::TableNotLoaded => write!(f, "range table is not loaded"),
+            RangeError::TableAssignment => write!(f, "range table assignment is rejected"),
+            RangeError::DuplicateColumn { index } => write!(
+                f,
+                "advice column {index} is given for more than one lookup"
+            ),
+            RangeError::TableTooLarge {
+                k,
+                required_k,
+                bit_len,
+                table_len,
+            } => write!(
+                f,
+                "range chip table of {table_len} rows with {bit_len} bit limbs doesn't fit in k = {k}, minimum k is {required_k}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+impl From<RangeError> for Error {
+    fn from(error: RangeError) -> Self {
+        match error {
+            RangeError::TableTooLarge { k, .. } => Error::NotEnoughRowsAvailable { current_k: k },
+            _ => Error::Synthesis,
+        }
+    }
+}
+
+impl RangeConfig {
+    /// Returns config of the main gate that range chip is built on
+    pub fn main_gate_config(&self) -> &MainGateConfig {
+        &self.main_gate_config
+    }
+
+    /// Returns advice columns that limbs are looked up at
+    pub fn lookup_advices(&self) -> [Column<Advice>; NUMBER_OF_LOOKUP_LIMBS] {
+        let &MainGateConfig { a, b, c, d, .. } = &self.main_gate_config;
+        [a, b, c, d]
+    }
+
+    /// Returns tag and value columns of the lookup table
+    pub fn table(&self) -> (TableColumn, TableColumn) {
+        (self.t_tag, self.t_value)
+    }
+
+    /// Returns the tag of given bit length in the lookup table
+    pub fn bit_len_tag(&self, bit_len: usize) -> Option<usize> {
+        self.bit_len_tag.get(&bit_len).copied()
+    }
+
+    /// Returns selector or fixed column that enables composition lookups
+    pub fn composition_selector(&self) -> LookupEnable {
+        self.s_composition
+    }
+
+    /// Returns selector or fixed column that enables overflow lookup if any
+    /// overflow bit length is configured
+    pub fn overflow_selector(&self) -> Option<LookupEnable> {
+        self.s_overflow
+    }
+
+    /// Returns the tag of given bound in the lookup table
+    pub fn bound_tag(&self, bound: u64) -> Option<usize> {
+        self.bound_tag.get(&bound).copied()
+    }
+
+    /// Returns number of rows of the lookup table, including the zero row
+    pub fn table_len(&self) -> usize {
+        1 + self
+            .bit_len_tag
+            .keys()
+            .map(|bit_len| 1 << bit_len)
+            .sum::<usize>()
+            + self
+                .bound_tag
+                .keys()
+                .map(|bound| *bound as usize)
+                .sum::<usize>()
+            + self.set.as_ref().map_or(0, |(_, values)| values.len())
+            + self
+                .bitwise
+                .map_or(0, |(bit_len, _)| BitwiseOp::ALL.len() << (2 * bit_len))
+    }
+
+    /// Returns `(tag, value)` rows of the lookup table in the order they are
+    /// loaded. Rows are generated on the fly, starting with the zero row
+    pub fn table_values<F: PrimeField>(&self) -> impl Iterator<Item = (F, F)> + '_ {
+        // Values below `2^bit_len` for bit lengths, below the bound for bounds
+        let ranges = self
+            .bit_len_tag
+            .iter()
+            .map(|(bit_len, tag)| (*tag, 1u64 << bit_len))
+            .chain(self.bound_tag.iter().map(|(bound, tag)| (*tag, *bound)))
+            .flat_map(|(tag, end)| (0..end).map(move |value| (tag, F::from(value))));
+        let set = self.set.iter().flat_map(|(tag, values)| {
+            values
+                .iter()
+                .map(move |value| (*tag, big_to_fe::<F>(value.clone())))
+        });
+        // Triples `a + b * 2^bit_len + (a op b) * 2^(2 * bit_len)`
+        let bitwise = self.bitwise.iter().flat_map(|&(bit_len, tag)| {
+            BitwiseOp::ALL.into_iter().flat_map(move |op| {
+                (0..1u64 << (2 * bit_len)).map(move |ab| {
+                    let (a, b) = (ab & ((1 << bit_len) - 1), ab >> bit_len);
+                    (
+                        tag + op as usize,
+                        F::from(ab + (op.apply(a, b) << (2 * bit_len))),
+                    )
+                })
+            })
+        });
+
+        // Rows with disabled selectors look up `(0, 0)`, which is loaded under
+        // no tag regardless of bounds or set values
+        std::iter::once((0, F::ZERO))
+            .chain(ranges)
+            .chain(set)
+            .chain(bitwise)
+            .map(|(tag, value)| (F::from(tag as u64), value))
+    }
+
+    /// Returns number of rows that a decomposition of `bit_len` bits into
+    /// `limb_bit_len` sized limbs occupies in the region
+    pub fn number_of_rows(&self, limb_bit_len: usize, bit_len: usize) -> usize {
+        assert!(bit_len > 0, "bit length is expected to be non zero");
+        let number_of_limbs = (bit_len - 1) / limb_bit_len + 1;
+        (number_of_limbs - 1) / self.number_of_lookup_limbs + 1
+    }
+
+    /// Returns the cost of `number_of_decompositions` decompositions of
+    /// `bit_len` bits into `limb_bit_len` sized limbs
+    pub fn cost(
+        &self,
+        number_of_decompositions: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> RangeCost {
+        RangeCost {
+            rows: number_of_decompositions * self.number_of_rows(limb_bit_len, bit_len),
+            lookup_arguments: if self.packed {
+                self.number_of_lookup_limbs / 2
+            } else {
+                self.number_of_lookup_limbs
+            } + if self.s_overflow.is_some() { 1 } else { 0 }
+                + if self.s_membership.is_some() { 1 } else { 0 }
+                + if self.s_bitwise.is_some() { 4 } else { 0 }
+                + if self.s_dynamic.is_some() { 1 } else { 0 },
+            lookup_advices: self.number_of_lookup_limbs,
+            table_rows: self.table_len(),
+        }
+    }
+
+    /// Returns the smallest `k` such that both the lookup table and
+    /// `number_of_rows` rows of the circuit fit in usable rows.
+    /// `blinding_factors` is expected to be taken from the constraint system
+    /// after configuration
+    pub fn min_k(&self, blinding_factors: usize, number_of_rows: usize) -> u32 {
+        // halo2 reserves blinding rows and one more row at the end
+        let n = self.table_len().max(number_of_rows) + blinding_factors + 1;
+        usize::BITS - (n - 1).leading_zeros()
+    }
+
+    /// Returns [`RangeError::TableTooLarge`] if the lookup table doesn't fit
+    /// in the usable rows of a circuit of size `2^k`. Meant to be called
+    /// before running a prover to fail early instead of at table assignment
+    pub fn check_k(&self, k: u32, blinding_factors: usize) -> Result<(), RangeError> {
+        if k < self.min_k(blinding_factors, 0) {
+            return Err(self.table_too_large(k, blinding_factors));
+        }
+        Ok(())
+    }
+
+    /// Returns [`RangeError::TableTooLarge`] for a circuit of size `2^k`.
+    /// Required size is at least `k + 1` since `k` is known to be too small
+    fn table_too_large(&self, k: u32, blinding_factors: usize) -> RangeError {
+        RangeError::TableTooLarge {
+            k,
+            required_k: self.min_k(blinding_factors, 0).max(k + 1),
+            bit_len: self.bit_len_tag.keys().max().copied().unwrap_or(0),
+            table_len: self.table_len(),
+        }
+    }
 }
 
 /// ['RangeChip'] applies binary range constraints
@@ -39,6 +378,8 @@ pub struct RangeChip<F: PrimeField> {
     config: RangeConfig,
     main_gate: MainGate<F>,
     bases: BTreeMap<usize, Vec<F>>,
+    // Shared with clones of this chip but not with other chips of the config
+    loaded: Arc<AtomicBool>,
 }
 
 impl<F: PrimeField> RangeChip<F> {
@@ -60,7 +401,8 @@ impl<F: PrimeField> Chip<F> for RangeChip<F> {
 
 /// Generic chip interface for bitwise ranging values
 pub trait RangeInstructions<F: PrimeField>: Chip<F> {
-    /// Assigns new witness
+    /// Assigns new witness. Returns [`Error::Synthesis`] if a known witness
+    /// doesn't fit in `bit_len` bits
     fn assign(
         &self,
         ctx: &mut RegionCtx<'_, F>,
@@ -69,7 +411,37 @@ pub trait RangeInstructions<F: PrimeField>: Chip<F> {
         bit_len: usize,
     ) -> Result<AssignedValue<F>, Error>;
 
-    /// Decomposes and assign new witness
+    /// Assigns new witness that is ranged to `bit_len` bits with the limb size
+    /// that takes the fewest rows among configured bit lengths. This is the
+    /// recommended entry point unless limbs are needed in a particular size.
+    /// Returns [`Error::Synthesis`] if a known witness doesn't fit
+    fn range_check(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Decomposes and assign new witness. Limbs are placed
+    /// [`NUMBER_OF_LOOKUP_LIMBS`] per row next to the running sum, with the
+    /// witness itself at the first row, so no filler rows are used:
+    ///
+    /// | A   | B   | C   | D   | E       |
+    /// | --- | --- | --- | --- | ------- |
+    /// | l_0 | l_1 | l_2 | l_3 | witness |
+    /// | l_7 | l_4 | l_5 | l_6 | r_1     |
+    ///
+    /// The most significant limb of the last row is moved to the first column
+    /// for the overflow lookup. Row cost is the number of limbs divided by
+    /// [`NUMBER_OF_LOOKUP_LIMBS`] rounded up, or the number of limbs for
+    /// chips configured with [`RangeChip::configure_narrow`]. Returns
+    /// [`Error::Synthesis`] if a known witness doesn't fit in `bit_len` bits
+    /// rather than decomposing a truncated value.
+    ///
+    /// Chips configured with [`RangeChip::configure_packed`] only range pairs
+    /// of limbs as `a + b * 2^limb_bit_len`, so a returned limb is not
+    /// constrained to `limb_bit_len` bits on its own. Callers that rely on
+    /// single limbs are expected to range them again.
     fn decompose(
         &self,
         ctx: &mut RegionCtx<'_, F>,
@@ -78,463 +450,6399 @@ pub trait RangeInstructions<F: PrimeField>: Chip<F> {
         bit_len: usize,
     ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error>;
 
-    /// Load table in sythnesis time
-    fn load_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error>;
-}
-
-impl<F: PrimeField> RangeInstructions<F> for RangeChip<F> {
-    fn assign(
+    /// Decomposes and assigns new witness like [`RangeInstructions::decompose`]
+    /// and returns limbs in given order, so that limb `i` of big endian `n`
+    /// limbs carries the base `2^((n - 1 - i) * limb_bit_len)`. Limbs are
+    /// placed in the composition rows in the same way for both orders since
+    /// the most significant limb is the one that is checked by the overflow
+    /// lookup
+    fn decompose_with_endianness(
         &self,
         ctx: &mut RegionCtx<'_, F>,
         unassigned: Value<F>,
         limb_bit_len: usize,
         bit_len: usize,
-    ) -> Result<AssignedValue<F>, Error> {
-        let (assigned, _) = self.decompose(ctx, unassigned, limb_bit_len, bit_len)?;
-        Ok(assigned)
+        endianness: Endianness,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        let (assigned, mut limbs) = self.decompose(ctx, unassigned, limb_bit_len, bit_len)?;
+        if endianness == Endianness::BigEndian {
+            limbs.reverse();
+        }
+        Ok((assigned, limbs))
     }
 
-    fn decompose(
+    /// Decomposes and assigns new witness into little endian bits using 1 bit
+    /// composition lookups, placing [`NUMBER_OF_LOOKUP_LIMBS`] bits per row.
+    /// 1 bit composition table is expected to be configured. Bits of a chip
+    /// configured with [`RangeChip::configure_packed`] are only ranged in
+    /// pairs, so they are not constrained to be boolean
+    fn to_bits(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        number_of_bits: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedCondition<F>>), Error>;
+
+    /// Decomposes a new witness into little endian bytes. 8 bit composition
+    /// table is expected to be configured. Notice that decomposition is not
+    /// unique if bytes cover the native modulus, e.g. 32 bytes in a 255 bit
+    /// field. Bytes of a chip configured with [`RangeChip::configure_packed`]
+    /// are only ranged in pairs
+    fn to_bytes(
         &self,
         ctx: &mut RegionCtx<'_, F>,
         unassigned: Value<F>,
+        number_of_bytes: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error>;
+
+    /// Decomposes a big integer that might not fit in the native field into
+    /// `limb_bit_len` sized limbs without reducing it. Integer is split into
+    /// chunks that fit in the native field and each chunk is decomposed
+    /// separately. Returns [`Error::Synthesis`] if a known integer doesn't
+    /// fit in `bit_len` bits
+    fn decompose_big(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<BigUint>,
         limb_bit_len: usize,
         bit_len: usize,
-    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
-        let (number_of_limbs, overflow_bit_len) = bit_len.div_rem(&limb_bit_len);
+    ) -> Result<Vec<AssignedValue<F>>, Error>;
 
-        let number_of_limbs = number_of_limbs + if overflow_bit_len > 0 { 1 } else { 0 };
-        let decomposed = unassigned
-            .map(|unassigned| decompose(unassigned, number_of_limbs, limb_bit_len))
-            .transpose_vec(number_of_limbs);
+    /// Decomposes an already assigned value and constrains the decomposed
+    /// witness to be equal to it
+    fn decompose_assigned(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        assigned: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error>;
 
-        let terms: Vec<Term<F>> = decomposed
-            .into_iter()
-            .zip(self.bases(limb_bit_len))
-            .map(|(limb, base)| Term::Unassigned(limb, *base))
-            .collect();
+    /// Decomposes a new witness that is bounded by `2^(bit_len +
+    /// overflow_bit_len)` into `bit_len` low bits and an overflow part. Low
+    /// bits are decomposed into `limb_bit_len` sized limbs and overflow is
+    /// ranged separately. Returns the witness, the low limbs and the overflow
+    fn decompose_with_overflow(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+        overflow_bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>, AssignedValue<F>), Error>;
 
-        self.main_gate()
-            .decompose(ctx, &terms[..], F::ZERO, |ctx, is_last| {
-                let composition_tag =
-                    self.config
-                        .bit_len_tag
-                        .get(&limb_bit_len)
-                        .unwrap_or_else(|| {
-                            panic!("composition table is not set, bit lenght: {limb_bit_len}")
-                        });
-                ctx.enable(self.config.s_composition)?;
-                if let Some(tag_composition) = self.config.tag_composition {
-                    ctx.assign_fixed(
-                        || "tag_composition",
-                        tag_composition,
-                        F::from(*composition_tag as u64),
-                    )?;
-                }
+    /// Assigns new witness as `quotient * 2^k + remainder` where remainder is
+    /// ranged to `k` bits and quotient to `quotient_bit_len` bits. Returns the
+    /// witness, the quotient and the remainder
+    fn div_rem_pow2(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        k: usize,
+        quotient_bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>, AssignedValue<F>), Error>;
 
-                if is_last && overflow_bit_len != 0 {
-                    let overflow_tag = self
-                        .config
-                        .bit_len_tag
-                        .get(&overflow_bit_len)
-                        .unwrap_or_else(|| {
-                            panic!("overflow table is not set, bit lenght: {overflow_bit_len}")
-                        });
-                    ctx.enable(self.config.s_overflow.unwrap())?;
-                    if let Some(tag_overflow) = self.config.tag_overflow {
-                        ctx.assign_fixed(
-                            || "tag_overflow",
-                            tag_overflow,
-                            F::from(*overflow_tag as u64),
-                        )?;
-                    }
-                }
+    /// Returns `hi * 2^k + lo` for already assigned halves. Halves are copied
+    /// into decomposition rows where `lo` is ranged to `k` bits and `hi` to
+    /// `bit_len - k` bits
+    fn merge(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        hi: &AssignedValue<F>,
+        lo: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
 
-                Ok(())
-            })
-    }
+    /// Splits already assigned value that fits in `bit_len` bits as `hi * 2^k
+    /// + lo` where `lo` is ranged to `k` bits and `hi` to `bit_len - k` bits.
+    /// Returns `hi` and `lo`
+    fn split(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error>;
 
-    fn load_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        layouter.assign_table(
-            || "",
-            |mut table| {
-                let mut offset = 0;
+    /// Returns `value >> k` for already assigned value that fits in `bit_len`
+    /// bits. Value is constrained as `hi * 2^k + lo` where `lo` is ranged to
+    /// `k` bits and `hi` to `bit_len - k` bits
+    fn shr_const(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
 
-                table.assign_cell(
-                    || "table tag",
-                    self.config.t_tag,
-                    offset,
-                    || Value::known(F::ZERO),
-                )?;
-                table.assign_cell(
-                    || "table value",
-                    self.config.t_value,
-                    offset,
-                    || Value::known(F::ZERO),
-                )?;
-                offset += 1;
-
-                for (bit_len, tag) in self.config.bit_len_tag.iter() {
-                    let tag = F::from(*tag as u64);
-                    let table_values: Vec<F> = (0..1 << bit_len).map(|e| F::from(e)).collect();
-                    for value in table_values.iter() {
-                        table.assign_cell(
-                            || "table tag",
-                            self.config.t_tag,
-                            offset,
-                            || Value::known(tag),
-                        )?;
-                        table.assign_cell(
-                            || "table value",
-                            self.config.t_value,
-                            offset,
-                            || Value::known(*value),
-                        )?;
-                        offset += 1;
-                    }
-                }
+    /// Reduces already assigned value by a constant modulus `m` as `value = q *
+    /// m + r` where quotient is ranged to `bit_len` bits and remainder is
+    /// constrained to be less than `m`. Returns the quotient and the remainder
+    fn mod_const(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        m: u64,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error>;
 
-                Ok(())
-            },
-        )?;
+    /// Decomposes `value * cond` so that the range check applies only if the
+    /// condition is set. Condition is constrained to be a bit
+    fn decompose_if(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        cond: &AssignedCondition<F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error>;
 
-        Ok(())
+    /// Assigns a constant that is known to fit in `bit_len` bits. Constant is
+    /// fixed by the main gate so no lookup is spent on it. Returns
+    /// [`Error::Synthesis`] if the constant doesn't fit
+    fn assign_constant_in_range(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        constant: F,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Composes already assigned limbs of `limb_bit_len` bits into a new
+    /// witness. If `range_limbs` is set limbs are copied into composition rows
+    /// and ranged again, otherwise only the composition is constrained
+    fn compose(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limbs: &[AssignedValue<F>],
+        limb_bit_len: usize,
+        range_limbs: bool,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Assigns new witness in a new region. Table is expected to be loaded
+    /// once with [`RangeInstructions::load_table`] in the same synthesis
+    fn range_value(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        layouter.assign_region(
+            || "range value",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                self.assign(ctx, unassigned, limb_bit_len, bit_len)
+            },
+        )
     }
-}
 
-impl<F: PrimeField> RangeChip<F> {
-    /// Given config creates new chip that implements ranging
-    pub fn new(config: RangeConfig) -> Self {
-        let main_gate = MainGate::new(config.main_gate_config.clone());
-        let bases = config
-            .bit_len_tag
-            .keys()
-            .filter_map(|&bit_len| {
-                if bit_len == 0 {
-                    None
-                } else {
-                    let bases = (0..F::NUM_BITS as usize / bit_len)
-                        .map(|i| F::from(2).pow(&[(bit_len * i) as u64, 0, 0, 0]))
-                        .collect();
-                    Some((bit_len, bases))
-                }
-            })
-            .collect();
-        Self {
-            config,
-            main_gate,
-            bases,
-        }
+    /// Assigns new witness that is constrained to be less than the given
+    /// bound. Alongside the witness `bound - 1 - witness` is also ranged
+    /// unless the bound is a power of two
+    fn assign_less_than_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bound: &BigUint,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Assigns new witnesses back to back in a single new region. Assigned
+    /// values are returned in the input order
+    fn range_values(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        unassigned: &[Value<F>],
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error> {
+        layouter.assign_region(
+            || "range values",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                unassigned
+                    .iter()
+                    .map(|unassigned| self.assign(ctx, *unassigned, limb_bit_len, bit_len))
+                    .collect()
+            },
+        )
     }
 
-    /// Configures subset argument and returns the
-    /// resuiting config
-    pub fn configure(
-        meta: &mut ConstraintSystem<F>,
-        main_gate_config: &MainGateConfig,
-        composition_bit_lens: Vec<usize>,
-        overflow_bit_lens: Vec<usize>,
-    ) -> RangeConfig {
-        let [composition_bit_lens, overflow_bit_lens] = [composition_bit_lens, overflow_bit_lens]
-            .map(|mut bit_lens| {
-                bit_lens.sort_unstable();
-                bit_lens.dedup();
-                bit_lens
-            });
+    /// Constrains each of already assigned values to fit in `bit_len` bits
+    /// where `bit_len` is one of composition bit lengths. Values are copied to
+    /// lookup columns without a composition, so a single row checks
+    /// [`NUMBER_OF_LOOKUP_LIMBS`] unrelated values
+    fn assert_small_values(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        values: &[AssignedValue<F>],
+        bit_len: usize,
+    ) -> Result<(), Error>;
 
-        let bit_len_tag = BTreeMap::from_iter(
-            BTreeSet::from_iter(composition_bit_lens.iter().chain(overflow_bit_lens.iter()))
-                .into_iter()
-                .enumerate()
-                .map(|(idx, bit_len)| (*bit_len, idx + 1)),
-        );
+    /// Returns `c = a - b + borrow * 2^bit_len` where `c` is ranged to
+    /// `bit_len` bits and borrow is a bit that is set if `a < b`. Both values
+    /// are expected to be already ranged to `bit_len` bits
+    fn sub_with_borrow(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error>;
 
-        let t_tag = meta.lookup_table_column();
-        let t_value = meta.lookup_table_column();
+    /// Constrains the integer composed of already ranged `limb_bit_len` sized
+    /// limbs to be less than the native modulus. Difference from `p - 1` is
+    /// computed limb by limb with a borrow chain, and each difference limb is
+    /// ranged
+    fn assert_canonical(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limbs: &[AssignedValue<F>],
+        limb_bit_len: usize,
+    ) -> Result<(), Error>;
 
-        // TODO: consider for a generic MainGateConfig
-        let &MainGateConfig { a, b, c, d, .. } = main_gate_config;
+    /// Constrains already assigned value to be less than a constant bound
+    /// that is not necessarily a power of two. Value is decomposed into
+    /// `limb_bit_len` sized limbs covering `bound - 1` and limbs are compared
+    /// against limbs of `bound - 1` from the most significant one, so high
+    /// limbs may be equal until the first smaller limb
+    fn assert_bounded_by_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bound: &BigUint,
+    ) -> Result<(), Error>;
 
-        let s_composition = meta.complex_selector();
+    /// Constrains already assigned value to be less than the given bound with
+    /// a single membership lookup. Bound is expected to be configured with
+    /// [`RangeChip::configure_with_bounds`]
+    fn assert_below_bound(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        bound: u64,
+    ) -> Result<(), Error>;
+
+    /// Constrains already assigned value to be in the set of values that is
+    /// configured with [`RangeChip::configure_with_values`]
+    fn assert_in_set(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error>;
+
+    /// Constrains `a < b` by ranging `b - a - 1` to `bit_len` bits. Both
+    /// values are expected to be already ranged to `bit_len` bits. Returns the
+    /// assigned difference
+    fn assert_less_than(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Constrains `a >= b` by ranging `a - b` to `bit_len` bits. Both values
+    /// are expected to be already ranged to `bit_len` bits. Returns the
+    /// assigned difference
+    fn assert_greater_equal(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Returns a bit that is set if `a < b`. Both values are expected to be
+    /// already ranged to `bit_len` bits. `a - b + 2^bit_len` is split into
+    /// `bit_len` low bits and a top bit, where the top bit is set if `a >= b`
+    fn is_less_than(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedCondition<F>, Error>;
+
+    /// Constrains `lo <= value < hi` where all values are expected to be
+    /// already ranged to `bit_len` bits. Returns assigned differences
+    /// `value - lo` and `hi - value - 1`
+    fn assert_in_interval(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        lo: &AssignedValue<F>,
+        hi: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error> {
+        let lower = self.assert_greater_equal(ctx, value, lo, limb_bit_len, bit_len)?;
+        let upper = self.assert_less_than(ctx, value, hi, limb_bit_len, bit_len)?;
+        Ok((lower, upper))
+    }
+
+    /// Assigns new witnesses `a` and `b` that are ranged to `bit_len` bits and
+    /// constrains `a < b`
+    fn assign_less_than(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: Value<F>,
+        b: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error> {
+        let a = self.assign(ctx, a, limb_bit_len, bit_len)?;
+        let b = self.assign(ctx, b, limb_bit_len, bit_len)?;
+        self.assert_less_than(ctx, &a, &b, limb_bit_len, bit_len)?;
+        Ok((a, b))
+    }
+
+    /// Returns a condition that is set if the value fits in `bit_len` bits.
+    /// Value is split into `bit_len` low bits and the remaining high bits up
+    /// to `max_bit_len`, and the condition is set if the high part is zero.
+    /// Values that don't fit in `max_bit_len` bits are not satisfiable
+    fn is_in_range(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+        max_bit_len: usize,
+    ) -> Result<AssignedCondition<F>, Error>;
+
+    /// Assigns a signed witness in `[-2^(bit_len-1), 2^(bit_len-1))` where
+    /// negative values are encoded as `p - |value|`. Witness is shifted by
+    /// `2^(bit_len-1)` so the range is mapped to `[0, 2^bit_len)`. Returns the
+    /// shifted witness and the sign which is set for negative witnesses, that
+    /// is when the shifted witness fits in `bit_len - 1` bits
+    fn assign_signed(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error>;
+
+    /// Splits already assigned value that represents a signed integer in
+    /// `(-2^bit_len, 2^bit_len)` into its absolute value ranged to `bit_len`
+    /// bits and a sign bit such that `value = (1 - 2 * sign) * abs`. Zero is
+    /// constrained to have positive sign. `-2^bit_len` is not representable
+    /// since its absolute value doesn't fit in `bit_len` bits
+    fn abs(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error>;
+
+    /// Copies already assigned value into a new row of the dynamic table that
+    /// is configured with [`RangeChip::configure_with_dynamic_table`]
+    fn push_table_row(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error>;
+
+    /// Constrains already assigned value to be one of the rows of the dynamic
+    /// table
+    fn assert_in_dynamic_table(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error>;
+
+    /// Returns bitwise AND of already assigned values. Operands and the
+    /// result are ranged to the bit length that is configured with
+    /// [`RangeChip::configure_with_bitwise`]
+    fn and(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Returns bitwise XOR of already assigned values. Operands and the
+    /// result are ranged to the bit length that is configured with
+    /// [`RangeChip::configure_with_bitwise`]
+    fn xor(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Returns bitwise OR of already assigned values. Operands and the
+    /// result are ranged to the bit length that is configured with
+    /// [`RangeChip::configure_with_bitwise`]
+    fn or(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error>;
+
+    /// Increments the offset with all zero selectors of the main gate and
+    /// range lookups
+    fn no_operation(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error>;
+
+    /// Assigns a padding row of zeros with all zero selectors
+    fn assign_zero_row(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error>;
+
+    /// Expect an assigned value to be equal to a public input
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        value: AssignedValue<F>,
+        row: usize,
+    ) -> Result<(), Error>;
+
+    /// Load table in sythnesis time. Loading is tracked per chip, so further
+    /// calls on the same chip or its clones are no-ops. Chips that are built
+    /// separately from the same owning config don't share this state and
+    /// only one of them should load the table, since a second assignment of
+    /// the table columns is rejected by the layouter. It is required in every
+    /// synthesis, otherwise lookups of non zero limbs fail and are reported
+    /// under the names of range chip lookups such as `composition_a`, or by
+    /// [`RangeChip::finalize`] in debug builds. Returns
+    /// [`RangeError::TableTooLarge`] with the suggested size of the circuit if
+    /// the table doesn't fit in the usable rows. Blinding rows are estimated
+    /// from the constraint system at configuration, so check size with
+    /// [`RangeConfig::check_k`] against the final constraint system
+    fn load_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), RangeError>;
+}
+
+impl<F: PrimeField> RangeInstructions<F> for RangeChip<F> {
+    fn range_check(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        let limb_bit_len = self.limb_bit_len_for(bit_len).unwrap_or_else(|| {
+            panic!("no configured limb size can range the bit length: {bit_len}")
+        });
+        self.assign(ctx, unassigned, limb_bit_len, bit_len)
+    }
+
+    fn assign(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        let (assigned, _) = self.decompose(ctx, unassigned, limb_bit_len, bit_len)?;
+        Ok(assigned)
+    }
+
+    fn decompose(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        Self::check_bit_len(unassigned, bit_len, ctx.offset())?;
+
+        let (number_of_limbs, overflow_bit_len) = bit_len.div_rem(&limb_bit_len);
+
+        let number_of_limbs = number_of_limbs + if overflow_bit_len > 0 { 1 } else { 0 };
+        let decomposed = unassigned
+            .map(|unassigned| decompose(unassigned, number_of_limbs, limb_bit_len))
+            .transpose_vec(number_of_limbs);
+
+        self.decompose_unchecked(ctx, decomposed, limb_bit_len, bit_len)
+    }
+
+    fn to_bits(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        number_of_bits: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedCondition<F>>), Error> {
+        self.decompose(ctx, unassigned, 1, number_of_bits)
+    }
+
+    fn to_bytes(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        number_of_bytes: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        self.decompose(ctx, unassigned, 8, 8 * number_of_bytes)
+    }
+
+    fn decompose_big(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<BigUint>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error> {
+        check_bits(
+            unassigned
+                .as_ref()
+                .map(|unassigned| unassigned.bits() as usize),
+            bit_len,
+            ctx.offset(),
+        )?;
+
+        // Largest multiple of limb size that is below the native modulus
+        let chunk_bit_len = (F::NUM_BITS as usize - 1) / limb_bit_len * limb_bit_len;
+
+        let mut limbs = vec![];
+        let mut shift = 0;
+        while shift < bit_len {
+            let chunk_bit_len = chunk_bit_len.min(bit_len - shift);
+            let mask = (BigUint::one() << chunk_bit_len) - 1usize;
+            let chunk = unassigned
+                .as_ref()
+                .map(|unassigned| big_to_fe((unassigned >> shift) & mask));
+            let (_, chunk_limbs) = self.decompose(ctx, chunk, limb_bit_len, chunk_bit_len)?;
+            limbs.extend(chunk_limbs);
+            shift += chunk_bit_len;
+        }
+
+        Ok(limbs)
+    }
+
+    fn decompose_assigned(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        assigned: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error> {
+        let (decomposed, limbs) =
+            self.decompose(ctx, assigned.value().copied(), limb_bit_len, bit_len)?;
+        ctx.constrain_equal(assigned.cell(), decomposed.cell())?;
+        Ok(limbs)
+    }
+
+    fn decompose_with_overflow(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+        overflow_bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>, AssignedValue<F>), Error> {
+        assert!(
+            bit_len + overflow_bit_len < F::NUM_BITS as usize,
+            "bit length is too large, bit length: {}",
+            bit_len + overflow_bit_len
+        );
+        Self::check_bit_len(unassigned, bit_len + overflow_bit_len, ctx.offset())?;
+
+        let (low, overflow) = unassigned
+            .map(|unassigned| split_at(unassigned, bit_len))
+            .unzip();
+        let (low, limbs) = self.decompose(ctx, low, limb_bit_len, bit_len)?;
+        let overflow = self.assign(ctx, overflow, limb_bit_len, overflow_bit_len)?;
+        let assigned = self.main_gate().compose(
+            ctx,
+            &[
+                Term::Assigned(&low, F::ONE),
+                Term::Assigned(&overflow, power_of_two(bit_len)),
+            ],
+            F::ZERO,
+        )?;
+
+        Ok((assigned, limbs, overflow))
+    }
+
+    fn div_rem_pow2(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        k: usize,
+        quotient_bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>, AssignedValue<F>), Error> {
+        assert!(
+            k + quotient_bit_len < F::NUM_BITS as usize,
+            "bit length is too large, bit length: {}",
+            k + quotient_bit_len
+        );
+        Self::check_bit_len(unassigned, k + quotient_bit_len, ctx.offset())?;
+
+        let (remainder, quotient) = unassigned.map(|unassigned| split_at(unassigned, k)).unzip();
+        let remainder = self.assign(ctx, remainder, limb_bit_len, k)?;
+        let quotient = self.assign(ctx, quotient, limb_bit_len, quotient_bit_len)?;
+        let assigned = self.main_gate().compose(
+            ctx,
+            &[
+                Term::Assigned(&remainder, F::ONE),
+                Term::Assigned(&quotient, power_of_two(k)),
+            ],
+            F::ZERO,
+        )?;
+
+        Ok((assigned, quotient, remainder))
+    }
+
+    fn merge(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        hi: &AssignedValue<F>,
+        lo: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        assert!(
+            k > 0 && k < bit_len,
+            "split point is expected to be within the bit length, k: {k}, bit length: {bit_len}"
+        );
+        assert!(
+            bit_len < F::NUM_BITS as usize,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        self.decompose_assigned(ctx, lo, limb_bit_len, k)?;
+        self.decompose_assigned(ctx, hi, limb_bit_len, bit_len - k)?;
+        self.main_gate().compose(
+            ctx,
+            &[
+                Term::Assigned(lo, F::ONE),
+                Term::Assigned(hi, power_of_two(k)),
+            ],
+            F::ZERO,
+        )
+    }
+
+    fn split(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error> {
+        assert!(
+            k > 0 && k < bit_len,
+            "split point is expected to be within the bit length, k: {k}, bit length: {bit_len}"
+        );
+        let (assigned, hi, lo) =
+            self.div_rem_pow2(ctx, value.value().copied(), limb_bit_len, k, bit_len - k)?;
+        ctx.constrain_equal(assigned.cell(), value.cell())?;
+        Ok((hi, lo))
+    }
+
+    fn shr_const(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        k: usize,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        assert!(bit_len > 0, "bit length is expected to be positive");
+        assert!(
+            k <= bit_len,
+            "shift is larger than the bit length, shift: {k}, bit length: {bit_len}"
+        );
+
+        // Either of the parts is empty so only the value itself is ranged
+        if k == 0 || k == bit_len {
+            self.decompose_assigned(ctx, value, limb_bit_len, bit_len)?;
+            return if k == 0 {
+                Ok(value.clone())
+            } else {
+                self.main_gate().assign_constant(ctx, F::ZERO)
+            };
+        }
+
+        let (hi, _) = self.split(ctx, value, k, limb_bit_len, bit_len)?;
+        Ok(hi)
+    }
+
+    fn mod_const(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        m: u64,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedValue<F>), Error> {
+        assert!(m > 0, "modulus is expected to be positive");
+        // `q * m + r` must not wrap around the native modulus
+        assert!(
+            bit_len + (u64::BITS - m.leading_zeros()) as usize < F::NUM_BITS as usize,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        let m_big = BigUint::from(m);
+
+        let (quotient, remainder) = value
+            .value()
+            .map(|value| {
+                let (quotient, remainder) = fe_to_big(*value).div_rem(&m_big);
+                (big_to_fe::<F>(quotient), big_to_fe::<F>(remainder))
+            })
+            .unzip();
+        let quotient = self.assign(ctx, quotient, limb_bit_len, bit_len)?;
+        let remainder = self.main_gate().assign_value(ctx, remainder)?;
+        self.assert_bounded_by_constant(ctx, &remainder, limb_bit_len, &m_big)?;
+
+        self.main_gate().assert_zero_sum(
+            ctx,
+            &[
+                Term::Assigned(value, F::ONE),
+                Term::Assigned(&quotient, -F::from(m)),
+                Term::Assigned(&remainder, -F::ONE),
+            ],
+            F::ZERO,
+        )?;
+
+        Ok((quotient, remainder))
+    }
+
+    fn decompose_if(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        cond: &AssignedCondition<F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<Vec<AssignedValue<F>>, Error> {
+        let main_gate = self.main_gate();
+        main_gate.assert_bit(ctx, cond)?;
+        // zero is always in range so lookups are satisfied for unset condition
+        let masked = main_gate.mul(ctx, value, cond)?;
+        self.decompose_assigned(ctx, &masked, limb_bit_len, bit_len)
+    }
+
+    fn assign_less_than_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bound: &BigUint,
+    ) -> Result<AssignedValue<F>, Error> {
+        assert!(!bound.is_zero(), "bound is expected to be non zero");
+        let max = bound - 1usize;
+        let bit_len = (max.bits() as usize).max(1);
+        assert!(
+            bit_len < F::NUM_BITS as usize - 1,
+            "bound is too large, bit length: {bit_len}"
+        );
+
+        let assigned = self.assign(ctx, unassigned, limb_bit_len, bit_len)?;
+
+        // Bound that is a power of two is covered by the bit length check
+        if *bound != BigUint::one() << bit_len {
+            let max: F = big_to_fe(max);
+            let diff = self.assign(
+                ctx,
+                unassigned.map(|unassigned| max - unassigned),
+                limb_bit_len,
+                bit_len,
+            )?;
+            self.main_gate().assert_zero_sum(
+                ctx,
+                &[
+                    Term::assigned_to_add(&assigned),
+                    Term::assigned_to_add(&diff),
+                ],
+                -max,
+            )?;
+        }
+
+        Ok(assigned)
+    }
+
+    fn assign_constant_in_range(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        constant: F,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        Self::check_bit_len(Value::known(constant), bit_len, ctx.offset())?;
+        self.main_gate().assign_constant(ctx, constant)
+    }
+
+    fn compose(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limbs: &[AssignedValue<F>],
+        limb_bit_len: usize,
+        range_limbs: bool,
+    ) -> Result<AssignedValue<F>, Error> {
+        let bit_len = limbs.len() * limb_bit_len;
+        assert!(
+            bit_len < F::NUM_BITS as usize,
+            "composed value doesn't fit in the native field, bit length: {bit_len}"
+        );
+
+        if range_limbs {
+            let decomposed = limbs.iter().map(|limb| limb.value().copied()).collect();
+            let (composed, ranged) =
+                self.decompose_unchecked(ctx, decomposed, limb_bit_len, bit_len)?;
+            for (limb, ranged) in limbs.iter().zip(ranged.iter()) {
+                ctx.constrain_equal(limb.cell(), ranged.cell())?;
+            }
+            Ok(composed)
+        } else {
+            let terms: Vec<Term<F>> = limbs
+                .iter()
+                .zip(self.bases(limb_bit_len))
+                .map(|(limb, base)| Term::Assigned(limb, *base))
+                .collect();
+            self.main_gate().compose(ctx, &terms[..], F::ZERO)
+        }
+    }
+
+    fn assert_small_values(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        values: &[AssignedValue<F>],
+        bit_len: usize,
+    ) -> Result<(), Error> {
+        let main_gate = self.main_gate();
+        // Values are placed in main gate columns only, also in the wide layout
+        let chunk_len = self
+            .config
+            .number_of_lookup_limbs
+            .min(NUMBER_OF_LOOKUP_LIMBS);
+        for values in values.chunks(chunk_len) {
+            // No overflow limb so only composition lookups are enabled
+            self.enable_lookups(ctx, bit_len, bit_len, false)?;
+            // Zero coefficients leave the main gate trivially satisfied
+            let terms: Vec<Term<F>> = values
+                .iter()
+                .map(|value| Term::Assigned(value, F::ZERO))
+                .collect();
+            main_gate.apply(
+                ctx,
+                terms,
+                F::ZERO,
+                CombinationOptionCommon::OneLinerAdd.into(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn sub_with_borrow(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error> {
+        assert!(
+            bit_len < F::NUM_BITS as usize - 1,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        let main_gate = self.main_gate();
+
+        let (c, borrow) = a
+            .value()
+            .zip(b.value())
+            .map(|(a, b)| {
+                let (a, b) = (fe_to_big(*a), fe_to_big(*b));
+                if a < b {
+                    let c = (BigUint::one() << bit_len) + a - b;
+                    (big_to_fe(c), F::ONE)
+                } else {
+                    (big_to_fe(a - b), F::ZERO)
+                }
+            })
+            .unzip();
+        let borrow = main_gate.assign_bit(ctx, borrow)?;
+        let c = self.assign(ctx, c, limb_bit_len, bit_len)?;
+        main_gate.assert_zero_sum(
+            ctx,
+            &[
+                Term::Assigned(a, F::ONE),
+                Term::Assigned(b, -F::ONE),
+                Term::Assigned(&borrow, power_of_two(bit_len)),
+                Term::Assigned(&c, -F::ONE),
+            ],
+            F::ZERO,
+        )?;
+
+        Ok((c, borrow))
+    }
+
+    fn assert_canonical(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limbs: &[AssignedValue<F>],
+        limb_bit_len: usize,
+    ) -> Result<(), Error> {
+        assert!(
+            limbs.len() * limb_bit_len >= F::NUM_BITS as usize,
+            "limbs are expected to cover the native modulus"
+        );
+        self.assert_limbs_at_most(ctx, limbs, limb_bit_len, &(modulus::<F>() - 1usize))
+    }
+
+    fn assert_bounded_by_constant(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bound: &BigUint,
+    ) -> Result<(), Error> {
+        assert!(!bound.is_zero(), "bound is expected to be positive");
+        assert!(
+            *bound <= modulus::<F>(),
+            "bound is expected to be at most the native modulus"
+        );
+        let max = bound - 1usize;
+        let number_of_limbs =
+            std::cmp::max((max.bits() as usize + limb_bit_len - 1) / limb_bit_len, 1);
+        let limbs =
+            self.decompose_assigned(ctx, value, limb_bit_len, number_of_limbs * limb_bit_len)?;
+        self.assert_limbs_at_most(ctx, &limbs, limb_bit_len, &max)
+    }
+
+    fn assert_below_bound(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        bound: u64,
+    ) -> Result<(), Error> {
+        let tag = self
+            .config
+            .bound_tag(bound)
+            .unwrap_or_else(|| panic!("bound table is not set, bound: {bound}"));
+        self.assert_member(ctx, value, tag)
+    }
+
+    fn assert_in_set(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error> {
+        let (tag, _) = self.config.set.as_ref().expect("set table is not set");
+        self.assert_member(ctx, value, *tag)
+    }
+
+    fn assert_less_than(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        // `b - a - 1` shouldn't wrap around the modulus for `a >= b`
+        assert!(
+            bit_len < F::NUM_BITS as usize - 1,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        let diff = self.main_gate().sub_with_constant(ctx, b, a, -F::ONE)?;
+        self.decompose_assigned(ctx, &diff, limb_bit_len, bit_len)?;
+        Ok(diff)
+    }
+
+    fn assert_greater_equal(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedValue<F>, Error> {
+        // `a - b` shouldn't wrap around the modulus for `a < b`
+        assert!(
+            bit_len < F::NUM_BITS as usize - 1,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        let diff = self.main_gate().sub(ctx, a, b)?;
+        self.decompose_assigned(ctx, &diff, limb_bit_len, bit_len)?;
+        Ok(diff)
+    }
+
+    fn is_less_than(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<AssignedCondition<F>, Error> {
+        assert!(
+            bit_len < F::NUM_BITS as usize - 1,
+            "bit length is too large, bit length: {bit_len}"
+        );
+        let main_gate = self.main_gate();
+        let base = power_of_two::<F>(bit_len);
+
+        let (low, top) = a
+            .value()
+            .zip(b.value())
+            .map(|(a, b)| split_at(*a - *b + base, bit_len))
+            .unzip();
+        let low = self.assign(ctx, low, limb_bit_len, bit_len)?;
+        let top = main_gate.assign_bit(ctx, top)?;
+        main_gate.assert_zero_sum(
+            ctx,
+            &[
+                Term::Assigned(a, F::ONE),
+                Term::Assigned(b, -F::ONE),
+                Term::Assigned(&low, -F::ONE),
+                Term::Assigned(&top, -base),
+            ],
+            base,
+        )?;
+
+        main_gate.not(ctx, &top)
+    }
+
+    fn is_in_range(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+        max_bit_len: usize,
+    ) -> Result<AssignedCondition<F>, Error> {
+        assert!(
+            bit_len < max_bit_len,
+            "expected bit length less than max bit length"
+        );
+        assert!(
+            max_bit_len < F::NUM_BITS as usize,
+            "max bit length is too large, bit length: {max_bit_len}"
+        );
+        let main_gate = self.main_gate();
+
+        let (low, high) = value.value().map(|value| split_at(*value, bit_len)).unzip();
+        let low = self.assign(ctx, low, limb_bit_len, bit_len)?;
+        let high = self.assign(ctx, high, limb_bit_len, max_bit_len - bit_len)?;
+        main_gate.assert_zero_sum(
+            ctx,
+            &[
+                Term::Assigned(&low, F::ONE),
+                Term::Assigned(&high, power_of_two(bit_len)),
+                Term::Assigned(value, -F::ONE),
+            ],
+            F::ZERO,
+        )?;
+
+        main_gate.is_zero(ctx, &high)
+    }
+
+    fn assign_signed(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        unassigned: Value<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error> {
+        assert!(bit_len > 1, "signed value needs at least two bits");
+        let half = power_of_two::<F>(bit_len - 1);
+        // `is_in_range` ranges the shifted value to `bit_len` bits
+        let shifted = self
+            .main_gate()
+            .assign_value(ctx, unassigned.map(|unassigned| unassigned + half))?;
+        let sign = self.is_in_range(ctx, &shifted, limb_bit_len, bit_len - 1, bit_len)?;
+        Ok((shifted, sign))
+    }
+
+    fn abs(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, AssignedCondition<F>), Error> {
+        let main_gate = self.main_gate();
+        let (abs, sign) = value
+            .value()
+            .map(|value| {
+                if fe_to_big(*value).bits() as usize <= bit_len {
+                    (*value, F::ZERO)
+                } else {
+                    (-*value, F::ONE)
+                }
+            })
+            .unzip();
+        let abs = self.assign(ctx, abs, limb_bit_len, bit_len)?;
+        let sign = main_gate.assign_bit(ctx, sign)?;
+
+        // `value - abs + 2 * sign * abs = 0`
+        main_gate.apply(
+            ctx,
+            [
+                Term::assigned_to_mul(&sign),
+                Term::Assigned(&abs, -F::ONE),
+                Term::assigned_to_mul(&sign),
+                Term::assigned_to_mul(&abs),
+                Term::assigned_to_add(value),
+            ],
+            F::ZERO,
+            CombinationOption::OneLinerDoubleMul(F::ONE),
+        )?;
+
+        // Negative zero would be a second representation of zero
+        let is_zero = main_gate.is_zero(ctx, &abs)?;
+        main_gate.nand(ctx, &sign, &is_zero)?;
+
+        Ok((abs, sign))
+    }
+
+    fn push_table_row(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error> {
+        let (s_table, _) = self.config.s_dynamic.expect("dynamic table is not set");
+        self.copy_with_selector(ctx, value, s_table)
+    }
+
+    fn assert_in_dynamic_table(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+    ) -> Result<(), Error> {
+        let (_, s_member) = self.config.s_dynamic.expect("dynamic table is not set");
+        self.copy_with_selector(ctx, value, s_member)
+    }
+
+    fn and(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error> {
+        self.bitwise(ctx, a, b, BitwiseOp::And)
+    }
+
+    fn xor(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error> {
+        self.bitwise(ctx, a, b, BitwiseOp::Xor)
+    }
+
+    fn or(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+    ) -> Result<AssignedValue<F>, Error> {
+        self.bitwise(ctx, a, b, BitwiseOp::Or)
+    }
+
+    fn no_operation(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error> {
+        // Lookup selectors are only enabled on decomposition rows
+        self.main_gate().no_operation(ctx)
+    }
+
+    fn assign_zero_row(&self, ctx: &mut RegionCtx<'_, F>) -> Result<(), Error> {
+        self.main_gate().assign_zero_row(ctx)
+    }
+
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        value: AssignedValue<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        self.main_gate().expose_public(layouter, value, row)
+    }
+
+    fn load_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), RangeError> {
+        // Shared table is loaded by its owner
+        if !self.config.owns_table || self.loaded.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        layouter
+            .assign_table(
+                || "",
+                |mut table| {
+                    // Rows are generated in chunks into a reused buffer, so
+                    // generation and assignment don't interleave row by row and
+                    // a large table is never held in memory at once
+                    let mut rows = self.config.table_values::<F>();
+                    let mut chunk = Vec::with_capacity(TABLE_CHUNK_LEN);
+                    let mut offset = 0;
+                    loop {
+                        chunk.clear();
+                        chunk.extend(rows.by_ref().take(TABLE_CHUNK_LEN));
+                        if chunk.is_empty() {
+                            break;
+                        }
+                        for (tag, value) in chunk.iter() {
+                            table.assign_cell(
+                                || "table tag",
+                                self.config.t_tag,
+                                offset,
+                                || Value::known(*tag),
+                            )?;
+                            table.assign_cell(
+                                || "table value",
+                                self.config.t_value,
+                                offset,
+                                || Value::known(*value),
+                            )?;
+                            offset += 1;
+                        }
+                    }
+
+                    Ok(())
+                },
+            )
+            .map_err(|e| match e {
+                Error::NotEnoughRowsAvailable { current_k } => self
+                    .config
+                    .table_too_large(current_k, self.config.blinding_factors),
+                _ => RangeError::TableAssignment,
+            })?;
+        self.loaded.store(true, Ordering::Release);
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> RangeChip<F> {
+    /// Given config creates new chip that implements ranging
+    pub fn new(config: RangeConfig) -> Self {
+        let main_gate = MainGate::new(config.main_gate_config.clone());
+        let bases = config
+            .bit_len_tag
+            .keys()
+            // Packed table covers pairs of limbs
+            .map(|&bit_len| if config.packed { bit_len / 2 } else { bit_len })
+            .filter_map(|bit_len| {
+                if bit_len == 0 {
+                    None
+                } else {
+                    // enough bases to cover a full width decomposition
+                    let bases = (0..(F::NUM_BITS as usize + bit_len - 1) / bit_len)
+                        .map(|i| F::from(2).pow(&[(bit_len * i) as u64, 0, 0, 0]))
+                        .collect();
+                    Some((bit_len, bases))
+                }
+            })
+            .collect();
+        Self {
+            config,
+            main_gate,
+            bases,
+            loaded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns [`RangeError::ValueTooLarge`] if a known value doesn't fit in
+    /// `bit_len` bits. This is the witness check of decompositions where
+    /// `offset` is the row that the value is going to be assigned at
+    pub fn check_bit_len(value: Value<F>, bit_len: usize, offset: usize) -> Result<(), RangeError> {
+        check_bits(
+            value.map(|value| fe_to_big(value).bits() as usize),
+            bit_len,
+            offset,
+        )
+    }
+
+    /// Returns [`RangeError::TableNotLoaded`] if the chip owns the table and
+    /// hasn't loaded it yet. Chips of a config that shares the table of
+    /// another one are not tracked
+    pub fn check_loaded(&self) -> Result<(), RangeError> {
+        if self.config.owns_table && !self.loaded.load(Ordering::Acquire) {
+            return Err(RangeError::TableNotLoaded);
+        }
+        Ok(())
+    }
+
+    /// Meant to be called at the end of synthesis. In debug builds returns
+    /// [`RangeError::TableNotLoaded`] if the table is never loaded, so a
+    /// forgotten [`RangeInstructions::load_table`] fails synthesis instead of
+    /// the lookups. It is a no-op in release builds
+    pub fn finalize(&self) -> Result<(), RangeError> {
+        #[cfg(debug_assertions)]
+        self.check_loaded()?;
+        Ok(())
+    }
+
+    /// Returns number of rows that a decomposition of `bit_len` bits into
+    /// `limb_bit_len` sized limbs occupies in the region
+    pub fn number_of_rows(&self, limb_bit_len: usize, bit_len: usize) -> usize {
+        self.config.number_of_rows(limb_bit_len, bit_len)
+    }
+
+    /// Returns the limb size that ranges `bit_len` bits in the fewest rows.
+    /// Candidates are composition widths only and leftover bits of a candidate
+    /// must be an overflow width. Among equally costly candidates the largest
+    /// limb size is chosen
+    pub fn limb_bit_len_for(&self, bit_len: usize) -> Option<usize> {
+        self.config
+            .composition_bit_lens
+            .iter()
+            .rev()
+            .copied()
+            .filter(|limb_bit_len| {
+                let overflow_bit_len = bit_len % limb_bit_len;
+                overflow_bit_len == 0 || self.config.overflow_bit_lens.contains(&overflow_bit_len)
+            })
+            .min_by_key(|limb_bit_len| self.number_of_rows(*limb_bit_len, bit_len))
+    }
+
+    /// Configures subset argument and returns the
+    /// resuiting config
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        )
+    }
+
+    /// Same as [`RangeChip::configure`] but composition and overflow lookups
+    /// are enabled with fixed columns instead of complex selectors. Accepted
+    /// witnesses and the degree of the constraint system are the same
+    pub fn configure_with_fixed_enable(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            true,
+        )
+    }
+
+    /// Same as [`RangeChip::configure`] but returns
+    /// [`RangeError::DuplicateColumn`] instead of panicking if a lookup column
+    /// of the main gate config is repeated. Constraint system is left as is on
+    /// error
+    pub fn try_configure(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> Result<RangeConfig, RangeError> {
+        Self::check_lookup_columns(main_gate_config, NUMBER_OF_LOOKUP_LIMBS)?;
+        Ok(Self::configure(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+        ))
+    }
+
+    /// Configures a new main gate and subset argument on top of it. Main gate
+    /// config is available with [`RangeConfig::main_gate_config`]. Both share
+    /// the five advice columns: the main gate queries them at the current row
+    /// and `e` at the next row, lookups query `a..d` at the current row only
+    /// under their own selectors. So main gate and range rows can be
+    /// interleaved in a region
+    pub fn configure_standalone(
+        meta: &mut ConstraintSystem<F>,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        let main_gate_config = MainGate::<F>::configure(meta);
+        Self::configure(
+            meta,
+            &main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+        )
+    }
+
+    /// Configures subset argument alongside a membership lookup for values
+    /// less than each of given bounds. Bounds don't have to be powers of two
+    pub fn configure_with_bounds(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+        bounds: Vec<u64>,
+    ) -> RangeConfig {
+        Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            bounds,
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        )
+    }
+
+    /// Configures subset argument where limbs are looked up in pairs as `a +
+    /// b * 2^limb_bit_len` and `c + d * 2^limb_bit_len` against a table of
+    /// `2 * limb_bit_len` bits. Halves lookup arguments per row in exchange
+    /// for a quadratically larger table, so it suits small limbs. Only the
+    /// decomposed value is ranged, a single limb of a pair may exceed
+    /// `limb_bit_len` bits as long as the pair fits. Decompositions are
+    /// expected to cover whole pairs of limbs and overflow limbs are not
+    /// supported
+    pub fn configure_packed(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        limb_bit_len: usize,
+    ) -> RangeConfig {
+        let packed_bit_len = 2 * limb_bit_len;
+        assert!(
+            (packed_bit_len as u32) < usize::BITS,
+            "lookup table is too large, bit length: {packed_bit_len}"
+        );
+        let bit_len_tag = BTreeMap::from([(packed_bit_len, 1)]);
+        let (t_tag, t_value) = (meta.lookup_table_column(), meta.lookup_table_column());
+
+        let &MainGateConfig { a, b, c, d, .. } = main_gate_config;
+        let s_composition = meta.complex_selector();
+        let tag = F::from(bit_len_tag[&packed_bit_len] as u64);
+        let base = power_of_two::<F>(limb_bit_len);
+        for (name, lo, hi) in [("composition_ab", a, b), ("composition_cd", c, d)] {
+            meta.lookup(name, |meta| {
+                let selector = meta.query_selector(s_composition);
+                let tag = selector.clone() * Expression::Constant(tag);
+                let lo = meta.query_advice(lo, Rotation::cur());
+                let hi = meta.query_advice(hi, Rotation::cur());
+                vec![
+                    (tag, t_tag),
+                    (selector * (lo + hi * Expression::Constant(base)), t_value),
+                ]
+            });
+        }
+
+        RangeConfig {
+            main_gate_config: main_gate_config.clone(),
+            bit_len_tag,
+            composition_bit_lens: BTreeSet::from([limb_bit_len]),
+            overflow_bit_lens: BTreeSet::new(),
+            t_tag,
+            t_value,
+            s_composition: LookupEnable::Selector(s_composition),
+            tag_composition: None,
+            s_overflow: None,
+            tag_overflow: None,
+            bound_tag: BTreeMap::new(),
+            set: None,
+            s_membership: None,
+            tag_membership: None,
+            bitwise: None,
+            s_bitwise: None,
+            tag_bitwise: None,
+            s_dynamic: None,
+            owns_table: true,
+            blinding_factors: meta.blinding_factors(),
+            number_of_lookup_limbs: NUMBER_OF_LOOKUP_LIMBS,
+            packed: true,
+            wide: None,
+        }
+    }
+
+    /// Configures subset argument with eight limbs per row. Four more advice
+    /// columns carry the upper half of each row next to the main gate
+    /// columns, so a full width decomposition takes half the rows in exchange
+    /// for four advice columns and four lookup arguments. Composition rows are
+    /// constrained by their own gate for `limb_bit_len` sized limbs only, the
+    /// main gate is left trivially satisfied on them
+    pub fn configure_wide(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        limb_bit_len: usize,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        let mut config = Self::configure_with(
+            meta,
+            main_gate_config,
+            vec![limb_bit_len],
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        );
+
+        let advices = [(); NUMBER_OF_LOOKUP_LIMBS].map(|_| {
+            let column = meta.advice_column();
+            meta.enable_equality(column);
+            column
+        });
+        // Single composition width takes a constant tag like the lookups of
+        // the main gate columns
+        let tag = config.bit_len_tag[&limb_bit_len];
+        let names = [
+            "composition_x0",
+            "composition_x1",
+            "composition_x2",
+            "composition_x3",
+        ];
+        for (name, column) in names.into_iter().zip(advices) {
+            Self::configure_lookup_with_constant_tag(
+                meta,
+                name,
+                config.s_composition,
+                tag,
+                column,
+                config.t_tag,
+                config.t_value,
+            );
+        }
+
+        let s_combine = meta.selector();
+        let s_last = [(); 2 * NUMBER_OF_LOOKUP_LIMBS].map(|_| meta.selector());
+        let &MainGateConfig { a, b, c, d, e, .. } = main_gate_config;
+        let columns: Vec<Column<Advice>> = [a, b, c, d].into_iter().chain(advices).collect();
+        let bases: Vec<F> = (0..=columns.len())
+            .map(|i| power_of_two::<F>(i * limb_bit_len))
+            .collect();
+        meta.create_gate("wide composition", |meta| {
+            let limbs: Vec<Expression<F>> = columns
+                .iter()
+                .map(|column| meta.query_advice(*column, Rotation::cur()))
+                .collect();
+            let sum = meta.query_advice(e, Rotation::cur());
+            let next = meta.query_advice(e, Rotation::next());
+
+            // r_i = l_0 + l_1 * B + ... + l_7 * B^7 + r_{i+1} * B^8
+            let s_combine = meta.query_selector(s_combine);
+            let combine = limbs.iter().zip(bases.iter()).fold(
+                next * Expression::Constant(bases[columns.len()]),
+                |acc, (limb, base)| acc + limb.clone() * Expression::Constant(*base),
+            );
+            vec![s_combine * (combine - sum)]
+        });
+        // Last row doesn't query the next one, so it is a separate gate
+        meta.create_gate("wide last composition", |meta| {
+            let limbs: Vec<Expression<F>> = columns
+                .iter()
+                .map(|column| meta.query_advice(*column, Rotation::cur()))
+                .collect();
+            let sum = meta.query_advice(e, Rotation::cur());
+
+            // Last row of `k` limbs keeps the top limb in the first column
+            // for the overflow lookup:
+            // r = a * B^(k-1) + l_0 + l_1 * B + ... + l_(k-2) * B^(k-2)
+            let last = s_last.iter().enumerate().map(|(i, s_last)| {
+                let s_last = meta.query_selector(*s_last);
+                let composed = limbs[1..i + 1].iter().zip(bases.iter()).fold(
+                    limbs[0].clone() * Expression::Constant(bases[i]),
+                    |acc, (limb, base)| acc + limb.clone() * Expression::Constant(*base),
+                );
+                s_last * (composed - sum.clone())
+            });
+            last.collect::<Vec<_>>()
+        });
+
+        config.number_of_lookup_limbs = 2 * NUMBER_OF_LOOKUP_LIMBS;
+        config.wide = Some(WideConfig {
+            advices,
+            s_combine,
+            s_last,
+            limb_bit_len,
+        });
+        config
+    }
+
+    /// Configures subset argument with a single composition lookup on the
+    /// first column. Limbs are placed one per row next to the running sum, so
+    /// a decomposition costs one row per limb instead of one row per
+    /// [`NUMBER_OF_LOOKUP_LIMBS`] limbs, in exchange for two lookup arguments
+    /// in total instead of five
+    pub fn configure_narrow(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            1,
+            false,
+        )
+    }
+
+    /// Configures subset argument alongside a membership lookup against rows
+    /// of the first advice column that are pushed with
+    /// [`RangeInstructions::push_table_row`]. Notice that the prover controls
+    /// the contents of this table, so it is sound only if the caller
+    /// constrains each pushed value
+    pub fn configure_with_dynamic_table(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> RangeConfig {
+        let mut config = Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        );
+
+        let s_table = meta.complex_selector();
+        let s_member = meta.complex_selector();
+        let a = main_gate_config.a;
+        // Selectors are looked up along with values so that disabled rows on
+        // both sides meet at `(0, 0)` and zero isn't a member by default
+        meta.lookup_any("dynamic_a", |meta| {
+            let s_table = meta.query_selector(s_table);
+            let s_member = meta.query_selector(s_member);
+            let a = meta.query_advice(a, Rotation::cur());
+            vec![
+                (s_member.clone(), s_table.clone()),
+                (s_member * a.clone(), s_table * a),
+            ]
+        });
+
+        config.s_dynamic = Some((s_table, s_member));
+        config
+    }
+
+    /// Configures subset argument alongside a lookup of bitwise AND, XOR and
+    /// OR of `bitwise_bit_len` sized operands. Table grows by `3 *
+    /// 2^(2 * bitwise_bit_len)` rows so the operand size is capped at 8 bits.
+    /// Operands and the result are each ranged to `bitwise_bit_len` bits in
+    /// the same row, otherwise the packed value `a + b * 2^n + c * 2^(2n)`
+    /// could be met by a result out of range
+    pub fn configure_with_bitwise(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+        bitwise_bit_len: usize,
+    ) -> RangeConfig {
+        assert!(
+            bitwise_bit_len > 0 && bitwise_bit_len <= 8,
+            "bitwise operands are expected to be at most 8 bits, bit length: {bitwise_bit_len}"
+        );
+        let mut config = Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        );
+
+        // Operand size gets its own range rows unless it is already in the table
+        let next_tag = config.bit_len_tag.len() + 1;
+        let tag_operand = *config
+            .bit_len_tag
+            .entry(bitwise_bit_len)
+            .or_insert(next_tag);
+        // Bitwise tags follow bit length tags, one per operation
+        let tag = config.bit_len_tag.len() + 1;
+        let s_bitwise = meta.complex_selector();
+        let tag_bitwise = meta.fixed_column();
+        let &MainGateConfig { a, b, c, .. } = main_gate_config;
+        let (t_tag, t_value) = config.table();
+        let [base_b, base_c] = [1, 2].map(|i| power_of_two::<F>(i * bitwise_bit_len));
+        meta.lookup("bitwise_abc", |meta| {
+            let selector = meta.query_selector(s_bitwise);
+            let tag = meta.query_fixed(tag_bitwise, Rotation::cur());
+            let [a, b, c] = [a, b, c].map(|column| meta.query_advice(column, Rotation::cur()));
+            let packed = a + b * Expression::Constant(base_b) + c * Expression::Constant(base_c);
+            vec![(tag, t_tag), (selector * packed, t_value)]
+        });
+        for (name, column) in [("bitwise_a", a), ("bitwise_b", b), ("bitwise_c", c)] {
+            Self::configure_lookup_with_constant_tag(
+                meta,
+                name,
+                s_bitwise,
+                tag_operand,
+                column,
+                t_tag,
+                t_value,
+            );
+        }
+
+        config.bitwise = Some((bitwise_bit_len, tag));
+        config.s_bitwise = Some(s_bitwise);
+        config.tag_bitwise = Some(tag_bitwise);
+        config
+    }
+
+    /// Configures subset argument alongside a membership lookup for the given
+    /// set of values. Duplicated values are ignored
+    pub fn configure_with_values(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+        values: Vec<F>,
+    ) -> RangeConfig {
+        let mut config = Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            None,
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        );
+
+        let mut values: Vec<BigUint> = values.into_iter().map(fe_to_big).collect();
+        values.sort_unstable();
+        values.dedup();
+        // Set tag follows bit length tags
+        let tag = config.bit_len_tag.len() + 1;
+        let (s_membership, tag_membership) =
+            Self::configure_membership(meta, main_gate_config, &config);
+        config.set = Some((tag, values));
+        config.s_membership = Some(s_membership);
+        config.tag_membership = Some(tag_membership);
+        config
+    }
+
+    /// Configures subset argument over the lookup table of an already
+    /// configured range chip. Given bit lengths are expected to be in the
+    /// shared table. Table is loaded only by the chip that owns it, so
+    /// [`RangeInstructions::load_table`] is a no-op for the returned config
+    pub fn configure_with_table(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+        shared: &RangeConfig,
+    ) -> RangeConfig {
+        Self::configure_with(
+            meta,
+            main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+            vec![],
+            Some(shared),
+            NUMBER_OF_LOOKUP_LIMBS,
+            false,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn configure_with(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+        mut bounds: Vec<u64>,
+        shared: Option<&RangeConfig>,
+        number_of_lookup_limbs: usize,
+        fixed_enable: bool,
+    ) -> RangeConfig {
+        let [composition_bit_lens, overflow_bit_lens] = [composition_bit_lens, overflow_bit_lens]
+            .map(|mut bit_lens| {
+                bit_lens.sort_unstable();
+                bit_lens.dedup();
+                bit_lens
+            });
+        bounds.sort_unstable();
+        bounds.dedup();
+        assert!(!bounds.contains(&0), "bound is expected to be non zero");
+
+        let (bit_len_tag, bound_tag, (t_tag, t_value), owns_table) = match shared {
+            Some(shared) => {
+                for bit_len in composition_bit_lens.iter().chain(overflow_bit_lens.iter()) {
+                    assert!(
+                        shared.bit_len_tag.contains_key(bit_len),
+                        "shared table is not set, bit length: {bit_len}"
+                    );
+                }
+                for bound in bounds.iter() {
+                    assert!(
+                        shared.bound_tag.contains_key(bound),
+                        "shared table is not set, bound: {bound}"
+                    );
+                }
+                (
+                    shared.bit_len_tag.clone(),
+                    shared.bound_tag.clone(),
+                    shared.table(),
+                    false,
+                )
+            }
+            None => {
+                for bit_len in composition_bit_lens.iter().chain(overflow_bit_lens.iter()) {
+                    assert!(
+                        (*bit_len as u32) < usize::BITS,
+                        "lookup table is too large, bit length: {bit_len}"
+                    );
+                }
+                let bit_len_tag = BTreeMap::from_iter(
+                    BTreeSet::from_iter(
+                        composition_bit_lens.iter().chain(overflow_bit_lens.iter()),
+                    )
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, bit_len)| (*bit_len, idx + 1)),
+                );
+                // Bound tags follow bit length tags
+                let bound_tag = BTreeMap::from_iter(
+                    bounds
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, bound)| (*bound, bit_len_tag.len() + idx + 1)),
+                );
+                let table = (meta.lookup_table_column(), meta.lookup_table_column());
+                (bit_len_tag, bound_tag, table, true)
+            }
+        };
+
+        // TODO: consider for a generic MainGateConfig
+        let &MainGateConfig { a, b, c, d, .. } = main_gate_config;
+        let composition_columns = [
+            ("composition_a", a),
+            ("composition_b", b),
+            ("composition_c", c),
+            ("composition_d", d),
+        ];
+        let composition_columns = &composition_columns[..number_of_lookup_limbs];
+        Self::check_lookup_columns(main_gate_config, number_of_lookup_limbs)
+            .unwrap_or_else(|e| panic!("lookup columns are expected to be distinct: {e}"));
+
+        let s_composition = LookupEnable::configure(meta, fixed_enable);
         let tag_composition = if composition_bit_lens.len() > 1 {
             let tag = meta.fixed_column();
-            for (name, value) in [
-                ("composition_a", a),
-                ("composition_b", b),
-                ("composition_c", c),
-                ("composition_d", d),
+            for &(name, value) in composition_columns.iter() {
+                Self::configure_lookup_with_column_tag(
+                    meta,
+                    name,
+                    s_composition,
+                    tag,
+                    value,
+                    t_tag,
+                    t_value,
+                )
+            }
+            Some(tag)
+        } else {
+            for &(name, value) in composition_columns.iter() {
+                Self::configure_lookup_with_constant_tag(
+                    meta,
+                    name,
+                    s_composition,
+                    bit_len_tag[&composition_bit_lens[0]],
+                    value,
+                    t_tag,
+                    t_value,
+                )
+            }
+            None
+        };
+
+        let (s_overflow, tag_overflow) = if !overflow_bit_lens.is_empty() {
+            let s_overflow = LookupEnable::configure(meta, fixed_enable);
+            let tag_overflow = if overflow_bit_lens.len() > 1 {
+                let tag = meta.fixed_column();
+                Self::configure_lookup_with_column_tag(
+                    meta,
+                    "overflow_a",
+                    s_overflow,
+                    tag,
+                    a,
+                    t_tag,
+                    t_value,
+                );
+                Some(tag)
+            } else {
+                Self::configure_lookup_with_constant_tag(
+                    meta,
+                    "overflow_a",
+                    s_overflow,
+                    bit_len_tag[&overflow_bit_lens[0]],
+                    a,
+                    t_tag,
+                    t_value,
+                );
+                None
+            };
+
+            (Some(s_overflow), tag_overflow)
+        } else {
+            (None, None)
+        };
+
+        let mut config = RangeConfig {
+            main_gate_config: main_gate_config.clone(),
+            bit_len_tag,
+            composition_bit_lens: BTreeSet::from_iter(composition_bit_lens),
+            overflow_bit_lens: BTreeSet::from_iter(overflow_bit_lens),
+            t_tag,
+            t_value,
+            s_composition,
+            tag_composition,
+            s_overflow,
+            tag_overflow,
+            bound_tag,
+            set: None,
+            s_membership: None,
+            tag_membership: None,
+            bitwise: None,
+            s_bitwise: None,
+            tag_bitwise: None,
+            s_dynamic: None,
+            owns_table,
+            blinding_factors: meta.blinding_factors(),
+            number_of_lookup_limbs,
+            packed: false,
+            wide: None,
+        };
+
+        if !bounds.is_empty() {
+            let (s_membership, tag_membership) =
+                Self::configure_membership(meta, main_gate_config, &config);
+            config.s_membership = Some(s_membership);
+            config.tag_membership = Some(tag_membership);
+        }
+
+        config
+    }
+
+    /// Returns [`RangeError::DuplicateColumn`] if the same advice column is
+    /// among the first `number_of_lookup_limbs` lookup columns twice
+    fn check_lookup_columns(
+        main_gate_config: &MainGateConfig,
+        number_of_lookup_limbs: usize,
+    ) -> Result<(), RangeError> {
+        let &MainGateConfig { a, b, c, d, .. } = main_gate_config;
+        let mut indices = BTreeSet::new();
+        for column in [a, b, c, d].iter().take(number_of_lookup_limbs) {
+            if !indices.insert(column.index()) {
+                return Err(RangeError::DuplicateColumn {
+                    index: column.index(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Configures the membership lookup on the first column with a tag column
+    /// that selects a bound or the set
+    fn configure_membership(
+        meta: &mut ConstraintSystem<F>,
+        main_gate_config: &MainGateConfig,
+        config: &RangeConfig,
+    ) -> (Selector, Column<Fixed>) {
+        let s_membership = meta.complex_selector();
+        let tag = meta.fixed_column();
+        Self::configure_lookup_with_column_tag(
+            meta,
+            "membership_a",
+            s_membership,
+            tag,
+            main_gate_config.a,
+            config.t_tag,
+            config.t_value,
+        );
+        (s_membership, tag)
+    }
+
+    fn configure_lookup_with_column_tag(
+        meta: &mut ConstraintSystem<F>,
+        name: &'static str,
+        selector: impl Into<LookupEnable>,
+        tag: Column<Fixed>,
+        value: Column<Advice>,
+        t_tag: TableColumn,
+        t_value: TableColumn,
+    ) {
+        let selector = selector.into();
+        meta.lookup(name, |meta| {
+            let selector = selector.query(meta);
+            let tag = meta.query_fixed(tag, Rotation::cur());
+            let value = meta.query_advice(value, Rotation::cur());
+            vec![(tag, t_tag), (selector * value, t_value)]
+        });
+    }
+
+    fn configure_lookup_with_constant_tag(
+        meta: &mut ConstraintSystem<F>,
+        name: &'static str,
+        selector: impl Into<LookupEnable>,
+        tag: usize,
+        value: Column<Advice>,
+        t_tag: TableColumn,
+        t_value: TableColumn,
+    ) {
+        let selector = selector.into();
+        meta.lookup(name, |meta| {
+            let selector = selector.query(meta);
+            let tag = selector.clone() * Expression::Constant(F::from(tag as u64));
+            let value = meta.query_advice(value, Rotation::cur());
+            vec![(tag, t_tag), (selector * value, t_value)]
+        });
+    }
+
+    /// Assigns given limbs into composition rows enabling lookups for
+    /// `limb_bit_len` sized limbs and for the overflow limb if `bit_len` is
+    /// not a multiple of `limb_bit_len`. Limbs are not checked against the
+    /// witness that they are supposed to compose.
+    ///
+    /// Constraints are the same as in [`RangeInstructions::decompose`] but
+    /// nothing is validated at synthesis time, so non canonical or out of
+    /// range limbs end up in the circuit. Meant for negative tests and for
+    /// gadgets that fix up such limbs later
+    pub fn decompose_unchecked(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        decomposed: Vec<Value<F>>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        if self.config.number_of_lookup_limbs == 1 {
+            return self.assign_limbs_narrow(ctx, decomposed, limb_bit_len, bit_len);
+        }
+        if self.config.packed {
+            return self.assign_limbs_packed(ctx, decomposed, limb_bit_len, bit_len);
+        }
+        if let Some(wide) = self.config.wide.as_ref() {
+            return self.assign_limbs_wide(ctx, wide, decomposed, limb_bit_len, bit_len);
+        }
+
+        let terms: Vec<Term<F>> = decomposed
+            .into_iter()
+            .zip(self.bases(limb_bit_len))
+            .map(|(limb, base)| Term::Unassigned(limb, *base))
+            .collect();
+
+        self.main_gate()
+            .decompose(ctx, &terms[..], F::ZERO, |ctx, is_last| {
+                self.enable_lookups(ctx, limb_bit_len, bit_len, is_last)
+            })
+    }
+
+    /// Assigns limbs one per row in the first column with the running sum in
+    /// the last column:
+    ///
+    /// | A   | E       |
+    /// | --- | ------- |
+    /// | l_0 | witness |
+    /// | l_1 | r_1     |
+    /// | l_2 | r_2     |
+    fn assign_limbs_narrow(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        decomposed: Vec<Value<F>>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        let main_gate = self.main_gate();
+        let terms: Vec<(Value<F>, F)> = decomposed
+            .into_iter()
+            .zip(self.bases(limb_bit_len).iter().copied())
+            .collect();
+        let number_of_limbs = terms.len();
+
+        let mut remaining = terms
+            .iter()
+            .fold(Value::known(F::ZERO), |acc, (limb, base)| {
+                acc + limb.map(|limb| limb * base)
+            });
+        let mut result = None;
+        let mut limbs = vec![];
+        for (i, (limb, base)) in terms.into_iter().enumerate() {
+            let is_last = i == number_of_limbs - 1;
+            self.enable_lookups(ctx, limb_bit_len, bit_len, is_last)?;
+
+            let option = if is_last {
+                CombinationOptionCommon::OneLinerAdd
+            } else {
+                CombinationOptionCommon::CombineToNextAdd(F::ONE)
+            };
+            let mut assigned = main_gate.apply(
+                ctx,
+                [
+                    Term::Unassigned(limb, base),
+                    Term::Zero,
+                    Term::Zero,
+                    Term::Zero,
+                    Term::Unassigned(remaining, -F::ONE),
+                ],
+                F::ZERO,
+                option.into(),
+            )?;
+
+            let intermediate = assigned.pop().unwrap();
+            if i == 0 {
+                result = Some(intermediate);
+            }
+            limbs.push(assigned.swap_remove(0));
+            remaining = remaining - limb.map(|limb| limb * base);
+        }
+
+        Ok((result.unwrap(), limbs))
+    }
+
+    /// Assigns limbs eight per row with the running sum in the last main gate
+    /// column. Upper half of a row goes to the wide columns and the top limb
+    /// of the last row is moved to the first column for the overflow lookup:
+    ///
+    /// | A    | B   | C   | D   | X_0 | X_1 | X_2 | X_3 | E       |
+    /// | ---- | --- | --- | --- | --- | --- | --- | --- | ------- |
+    /// | l_0  | l_1 | l_2 | l_3 | l_4 | l_5 | l_6 | l_7 | witness |
+    /// | l_10 | l_8 | l_9 | -   | -   | -   | -   | -   | r_1     |
+    fn assign_limbs_wide(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        wide: &WideConfig,
+        decomposed: Vec<Value<F>>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        assert_eq!(
+            limb_bit_len, wide.limb_bit_len,
+            "wide composition gate is set for another bit length: {limb_bit_len}"
+        );
+        let &MainGateConfig { a, b, c, d, e, .. } = &self.config.main_gate_config;
+        let columns: Vec<Column<Advice>> = [a, b, c, d].into_iter().chain(wide.advices).collect();
+        let base = power_of_two::<F>(limb_bit_len);
+
+        // Running sum of each row with the remaining rows below it
+        let rows: Vec<&[Value<F>]> = decomposed.chunks(columns.len()).collect();
+        let mut sums = vec![Value::known(F::ZERO); rows.len() + 1];
+        for (i, row) in rows.iter().enumerate().rev() {
+            sums[i] = row.iter().rev().fold(sums[i + 1], |acc, limb| {
+                acc.zip(*limb).map(|(acc, limb)| acc * base + limb)
+            });
+        }
+
+        let mut result = None;
+        let mut limbs = vec![];
+        for (i, row) in rows.iter().enumerate() {
+            let is_last = i == rows.len() - 1;
+            self.enable_lookups(ctx, limb_bit_len, bit_len, is_last)?;
+            let mut row = row.to_vec();
+            if is_last {
+                ctx.enable(wide.s_last[row.len() - 1])?;
+                row.rotate_right(1);
+            } else {
+                ctx.enable(wide.s_combine)?;
+            }
+
+            let mut assigned = vec![];
+            for (j, column) in columns.iter().enumerate() {
+                let limb = row.get(j).copied().unwrap_or_else(|| Value::known(F::ZERO));
+                let limb = ctx.assign_advice(|| "limb", *column, limb)?;
+                if j < row.len() {
+                    assigned.push(limb);
+                }
+            }
+            if is_last {
+                // Rewind the overflow range trick
+                assigned.rotate_left(1);
+            }
+            limbs.extend(assigned);
+
+            let sum = ctx.assign_advice(|| "sum", e, sums[i])?;
+            if i == 0 {
+                result = Some(sum);
+            }
+            ctx.next();
+        }
+
+        Ok((result.unwrap(), limbs))
+    }
+
+    /// Assigns limbs in order four per row with the running sum in the last
+    /// column, so that pairs `(a, b)` and `(c, d)` are consecutive limbs:
+    ///
+    /// | A   | B   | C   | D   | E       |
+    /// | --- | --- | --- | --- | ------- |
+    /// | l_0 | l_1 | l_2 | l_3 | witness |
+    /// | l_4 | l_5 | -   | -   | r_1     |
+    fn assign_limbs_packed(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        decomposed: Vec<Value<F>>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(AssignedValue<F>, Vec<AssignedValue<F>>), Error> {
+        let packed_bit_len = 2 * limb_bit_len;
+        assert!(
+            self.config.bit_len_tag.contains_key(&packed_bit_len),
+            "packed table is not set, bit length: {limb_bit_len}"
+        );
+        // A pair with a single limb would leave the limb ranged to the
+        // packed bit length
+        assert_eq!(
+            bit_len % packed_bit_len,
+            0,
+            "packed decomposition is expected to cover pairs of limbs, bit length: {bit_len}"
+        );
+        let main_gate = self.main_gate();
+        let terms: Vec<(Value<F>, F)> = decomposed
+            .into_iter()
+            .zip(self.bases(limb_bit_len).iter().copied())
+            .collect();
+        let number_of_rows = (terms.len() - 1) / NUMBER_OF_LOOKUP_LIMBS + 1;
+
+        let compose = |terms: &[(Value<F>, F)]| {
+            terms
+                .iter()
+                .fold(Value::known(F::ZERO), |acc, (limb, base)| {
+                    acc + limb.map(|limb| limb * base)
+                })
+        };
+        let mut remaining = compose(&terms);
+        let mut result = None;
+        let mut limbs = vec![];
+        for (i, chunk) in terms.chunks(NUMBER_OF_LOOKUP_LIMBS).enumerate() {
+            let is_last = i == number_of_rows - 1;
+            self.config.s_composition.enable(ctx)?;
+
+            let option = if is_last {
+                CombinationOptionCommon::OneLinerAdd
+            } else {
+                CombinationOptionCommon::CombineToNextAdd(F::ONE)
+            };
+            let mut assigned = main_gate.apply(
+                ctx,
+                chunk
+                    .iter()
+                    .map(|(limb, base)| Term::Unassigned(*limb, *base))
+                    .chain(std::iter::repeat(Term::Zero).take(NUMBER_OF_LOOKUP_LIMBS - chunk.len()))
+                    .chain(std::iter::once(Term::Unassigned(remaining, -F::ONE))),
+                F::ZERO,
+                option.into(),
+            )?;
+
+            let intermediate = assigned.pop().unwrap();
+            if i == 0 {
+                result = Some(intermediate);
+            }
+            limbs.extend(assigned.into_iter().take(chunk.len()));
+            remaining = remaining - compose(chunk);
+        }
+
+        Ok((result.unwrap(), limbs))
+    }
+
+    /// Constrains the integer composed of already ranged `limb_bit_len` sized
+    /// limbs to be at most `max`. Difference from `max` is computed limb by
+    /// limb with a borrow chain, and each difference limb is ranged. A borrow
+    /// out of the most significant limb makes its difference unrangeable
+    fn assert_limbs_at_most(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limbs: &[AssignedValue<F>],
+        limb_bit_len: usize,
+        max: &BigUint,
+    ) -> Result<(), Error> {
+        let number_of_limbs = limbs.len();
+        assert!(
+            number_of_limbs * limb_bit_len >= max.bits() as usize,
+            "limbs are expected to cover the maximum value"
+        );
+        let main_gate = self.main_gate();
+        let max_limbs = decompose_big::<F>(max.clone(), number_of_limbs, limb_bit_len);
+        let base = BigUint::one() << limb_bit_len;
+
+        let mut borrow: Option<AssignedCondition<F>> = None;
+        for (i, (limb, max_limb)) in limbs.iter().zip(max_limbs).enumerate() {
+            let is_last = i == number_of_limbs - 1;
+            let borrow_value = borrow
+                .as_ref()
+                .map(|borrow| borrow.value().copied())
+                .unwrap_or_else(|| Value::known(F::ZERO));
+
+            // `max_limb - limb - borrow + next_borrow * 2^limb_bit_len`, where
+            // a negative difference at the last limb is not rangeable
+            let (diff, next_borrow) = limb
+                .value()
+                .zip(borrow_value)
+                .map(|(limb, borrow)| {
+                    let lhs = fe_to_big(max_limb);
+                    let rhs = fe_to_big(*limb) + fe_to_big(borrow);
+                    if lhs >= rhs || is_last {
+                        (big_to_fe::<F>(lhs) - big_to_fe::<F>(rhs), F::ZERO)
+                    } else {
+                        (big_to_fe(&base + lhs - rhs), F::ONE)
+                    }
+                })
+                .unzip();
+            let diff = self.assign(ctx, diff, limb_bit_len, limb_bit_len)?;
+            let next_borrow = if is_last {
+                None
+            } else {
+                Some(main_gate.assign_bit(ctx, next_borrow)?)
+            };
+
+            {
+                let terms: Vec<Term<F>> = [
+                    Some(Term::Assigned(limb, F::ONE)),
+                    Some(Term::Assigned(&diff, F::ONE)),
+                    borrow.as_ref().map(|borrow| Term::Assigned(borrow, F::ONE)),
+                    next_borrow.as_ref().map(|next_borrow| {
+                        Term::Assigned(next_borrow, -power_of_two::<F>(limb_bit_len))
+                    }),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                main_gate.assert_zero_sum(ctx, &terms, -max_limb)?;
+            }
+
+            borrow = next_borrow;
+        }
+
+        Ok(())
+    }
+
+    /// Constrains the value to be in the table under the given tag with the
+    /// membership lookup
+    fn assert_member(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        tag: usize,
+    ) -> Result<(), Error> {
+        ctx.assign_fixed(
+            || "tag_membership",
+            self.config.tag_membership.unwrap(),
+            F::from(tag as u64),
+        )?;
+        self.copy_with_selector(ctx, value, self.config.s_membership.unwrap())
+    }
+
+    /// Copies already assigned value to the first column of a new row where
+    /// the given selector is enabled
+    fn copy_with_selector(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        value: &AssignedValue<F>,
+        selector: Selector,
+    ) -> Result<(), Error> {
+        ctx.enable(selector)?;
+        // Zero coefficient leaves the main gate trivially satisfied
+        self.main_gate().apply(
+            ctx,
+            [Term::Assigned(value, F::ZERO)],
+            F::ZERO,
+            CombinationOptionCommon::OneLinerAdd.into(),
+        )?;
+        Ok(())
+    }
+
+    /// Assigns `a op b` next to the operands and enables the bitwise lookup
+    /// with the tag of the operation
+    fn bitwise(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        a: &AssignedValue<F>,
+        b: &AssignedValue<F>,
+        op: BitwiseOp,
+    ) -> Result<AssignedValue<F>, Error> {
+        let (bit_len, tag) = self.config.bitwise.expect("bitwise table is not set");
+        // Result is only defined for operands that are in the table
+        let c = a.value().zip(b.value()).map(|(a, b)| {
+            let [a, b] = [a, b].map(|value| fe_to_big(*value));
+            if a.bits() as usize > bit_len || b.bits() as usize > bit_len {
+                F::ZERO
+            } else {
+                F::from(op.apply(a.to_u64().unwrap(), b.to_u64().unwrap()))
+            }
+        });
+
+        ctx.enable(self.config.s_bitwise.unwrap())?;
+        ctx.assign_fixed(
+            || "tag_bitwise",
+            self.config.tag_bitwise.unwrap(),
+            F::from((tag + op as usize) as u64),
+        )?;
+        // Zero coefficients leave the main gate trivially satisfied
+        let mut assigned = self.main_gate().apply(
+            ctx,
+            [
+                Term::Assigned(a, F::ZERO),
+                Term::Assigned(b, F::ZERO),
+                Term::Unassigned(c, F::ZERO),
+            ],
+            F::ZERO,
+            CombinationOptionCommon::OneLinerAdd.into(),
+        )?;
+        Ok(assigned.swap_remove(2))
+    }
+
+    /// Enables composition lookups at the current row and the overflow lookup
+    /// if it is the last row of a decomposition with an overflow limb
+    fn enable_lookups(
+        &self,
+        ctx: &mut RegionCtx<'_, F>,
+        limb_bit_len: usize,
+        bit_len: usize,
+        is_last: bool,
+    ) -> Result<(), Error> {
+        assert!(
+            !self.config.packed,
+            "packed layout ranges limbs only in pairs of a decomposition"
+        );
+        let overflow_bit_len = bit_len % limb_bit_len;
+        assert!(
+            self.config.composition_bit_lens.contains(&limb_bit_len),
+            "composition lookup is not set, bit length: {limb_bit_len}"
+        );
+        assert!(
+            overflow_bit_len == 0 || self.config.overflow_bit_lens.contains(&overflow_bit_len),
+            "overflow lookup is not set, bit length: {overflow_bit_len}"
+        );
+
+        let composition_tag = self
+            .config
+            .bit_len_tag
+            .get(&limb_bit_len)
+            .unwrap_or_else(|| panic!("composition table is not set, bit length: {limb_bit_len}"));
+        self.config.s_composition.enable(ctx)?;
+        if let Some(tag_composition) = self.config.tag_composition {
+            ctx.assign_fixed(
+                || "tag_composition",
+                tag_composition,
+                F::from(*composition_tag as u64),
+            )?;
+        }
+
+        if is_last && overflow_bit_len != 0 {
+            let overflow_tag = self
+                .config
+                .bit_len_tag
+                .get(&overflow_bit_len)
+                .unwrap_or_else(|| {
+                    panic!("overflow table is not set, bit length: {overflow_bit_len}")
+                });
+            self.config.s_overflow.unwrap().enable(ctx)?;
+            if let Some(tag_overflow) = self.config.tag_overflow {
+                ctx.assign_fixed(
+                    || "tag_overflow",
+                    tag_overflow,
+                    F::from(*overflow_tag as u64),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bases(&self, limb_bit_len: usize) -> &[F] {
+        self.bases
+            .get(&limb_bit_len)
+            .unwrap_or_else(|| panic!("composition table is not set, bit length: {}", limb_bit_len))
+            .as_slice()
+    }
+}
+
+/// Returns [`RangeError::ValueTooLarge`] if known number of bits exceeds
+/// `bit_len`
+fn check_bits(bits: Value<usize>, bit_len: usize, offset: usize) -> Result<(), RangeError> {
+    let mut result = Ok(());
+    bits.map(|bits| {
+        if bits > bit_len {
+            result = Err(RangeError::ValueTooLarge {
+                bits,
+                bit_len,
+                offset,
+            });
+        }
+    });
+    result
+}
+
+/// Splits a field element into its `bit_len` low bits and the remaining high
+/// bits
+fn split_at<F: PrimeField>(value: F, bit_len: usize) -> (F, F) {
+    let value = fe_to_big(value);
+    let mask = (BigUint::one() << bit_len) - 1usize;
+    (big_to_fe(&value & mask), big_to_fe(value >> bit_len))
+}
+
+/// Bitwise operations of the lookup table in the order of their tags
+#[derive(Clone, Copy, Debug)]
+enum BitwiseOp {
+    And,
+    Xor,
+    Or,
+}
+
+impl BitwiseOp {
+    const ALL: [BitwiseOp; 3] = [BitwiseOp::And, BitwiseOp::Xor, BitwiseOp::Or];
+
+    fn apply(self, a: u64, b: u64) -> u64 {
+        match self {
+            BitwiseOp::And => a & b,
+            BitwiseOp::Xor => a ^ b,
+            BitwiseOp::Or => a | b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use halo2wrong::halo2::arithmetic::Field;
+    use halo2wrong::halo2::circuit::Value;
+    use halo2wrong::utils::{big_to_fe, decompose, power_of_two};
+    use halo2wrong::RegionCtx;
+    use std::cell::Cell;
+    use std::iter;
+    use std::rc::Rc;
+
+    use super::{
+        split_at, BitwiseOp, Endianness, LookupEnable, RangeChip, RangeConfig, RangeError,
+        RangeInstructions, NUMBER_OF_LOOKUP_LIMBS, TABLE_CHUNK_LEN,
+    };
+    use crate::curves::{ff::PrimeField, pasta::Fp};
+    use crate::halo2::circuit::{Layouter, SimpleFloorPlanner};
+    use crate::halo2::dev::{MockProver, VerifyFailure};
+    use crate::halo2::plonk::{Circuit, ConstraintSystem, Error};
+    use crate::main_gate::{CombinationOption, MainGate, MainGateColumn};
+    use crate::test_utils::{self, Configure, TestConfig};
+    use crate::{AssignedValue, CombinationOptionCommon, MainGateInstructions, Term};
+
+    #[derive(Clone, Debug)]
+    struct TestCircuitConfig {
+        range_config: RangeConfig,
+    }
+
+    impl TestCircuitConfig {
+        fn new<F: PrimeField>(
+            meta: &mut ConstraintSystem<F>,
+            composition_bit_lens: Vec<usize>,
+            overflow_bit_lens: Vec<usize>,
+        ) -> Self {
+            let main_gate_config = MainGate::<F>::configure(meta);
+
+            let range_config = RangeChip::<F>::configure(
+                meta,
+                &main_gate_config,
+                composition_bit_lens,
+                overflow_bit_lens,
+            );
+            Self { range_config }
+        }
+
+        fn new_narrow<F: PrimeField>(
+            meta: &mut ConstraintSystem<F>,
+            composition_bit_lens: Vec<usize>,
+            overflow_bit_lens: Vec<usize>,
+        ) -> Self {
+            let main_gate_config = MainGate::<F>::configure(meta);
+
+            let range_config = RangeChip::<F>::configure_narrow(
+                meta,
+                &main_gate_config,
+                composition_bit_lens,
+                overflow_bit_lens,
+            );
+            Self { range_config }
+        }
+
+        fn main_gate<F: PrimeField>(&self) -> MainGate<F> {
+            MainGate::<F>::new(self.range_config.main_gate_config.clone())
+        }
+
+        fn range_chip<F: PrimeField>(&self) -> RangeChip<F> {
+            RangeChip::<F>::new(self.range_config.clone())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct Input<F: PrimeField> {
+        bit_len: usize,
+        limb_bit_len: usize,
+        value: Value<F>,
+    }
+
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuit<F: PrimeField> {
+        inputs: Vec<Input<F>>,
+    }
+
+    impl<F: PrimeField> TestCircuit<F> {
+        fn composition_bit_lens() -> Vec<usize> {
+            vec![8]
+        }
+
+        fn overflow_bit_lens() -> Vec<usize> {
+            vec![3]
+        }
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuit<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(
+                meta,
+                Self::composition_bit_lens(),
+                Self::overflow_bit_lens(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+            let main_gate = config.main_gate();
+
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+
+                    for input in self.inputs.iter() {
+                        let value = input.value;
+                        let limb_bit_len = input.limb_bit_len;
+                        let bit_len = input.bit_len;
+
+                        let a_0 = main_gate.assign_value(ctx, value)?;
+                        let (a_1, decomposed) =
+                            range_chip.decompose(ctx, value, limb_bit_len, bit_len)?;
+
+                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+
+                        let terms: Vec<Term<F>> = decomposed
+                            .iter()
+                            .zip(range_chip.bases(limb_bit_len))
+                            .map(|(limb, base)| Term::Assigned(limb, *base))
+                            .collect();
+                        let a_1 = main_gate.compose(ctx, &terms[..], F::ZERO)?;
+                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+                    }
+
+                    Ok(())
+                },
+            )?;
+
+            range_chip.load_table(&mut layouter)?;
+
+            Ok(())
+        }
+    }
+
+    /// Test circuit that assigns each input in its own region
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitRegions<F: PrimeField> {
+        inputs: Vec<Input<F>>,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitRegions<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(
+                meta,
+                TestCircuit::<F>::composition_bit_lens(),
+                TestCircuit::<F>::overflow_bit_lens(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+            let main_gate = config.main_gate();
+
+            for input in self.inputs.iter() {
+                let a_0 = range_chip.range_value(
+                    &mut layouter,
+                    input.value,
+                    input.limb_bit_len,
+                    input.bit_len,
+                )?;
+
+                layouter.assign_region(
+                    || "region 1",
+                    |region| {
+                        let offset = 0;
+                        let ctx = &mut RegionCtx::new(region, offset);
+                        let a_1 = main_gate.assign_value(ctx, input.value)?;
+                        main_gate.assert_equal(ctx, &a_0, &a_1)
+                    },
+                )?;
+            }
+
+            range_chip.load_table(&mut layouter)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_value_in_regions() {
+        const LIMB_BIT_LEN: usize = 8;
+        const OVERFLOW_BIT_LEN: usize = 3;
+        let k: u32 = (LIMB_BIT_LEN + 2) as u32;
+
+        let inputs = (1..10)
+            .map(|number_of_limbs| {
+                let bit_len = LIMB_BIT_LEN * number_of_limbs + OVERFLOW_BIT_LEN;
+                Input {
+                    value: Value::known(power_of_two::<Fp>(bit_len) - Fp::one()),
+                    limb_bit_len: LIMB_BIT_LEN,
+                    bit_len,
+                }
+            })
+            .collect();
+
+        let circuit = TestCircuitRegions::<Fp> { inputs };
+        let public_inputs = vec![vec![]];
+        let prover = match MockProver::run(k, &circuit, public_inputs) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_range_circuit() {
+        const LIMB_BIT_LEN: usize = 8;
+        const OVERFLOW_BIT_LEN: usize = 3;
+        let k: u32 = (LIMB_BIT_LEN + 1) as u32;
+
+        let inputs = (2..20)
+            .map(|number_of_limbs| {
+                let bit_len = LIMB_BIT_LEN * number_of_limbs + OVERFLOW_BIT_LEN;
+                let value = Fp::from(2).pow(&[bit_len as u64, 0, 0, 0]) - Fp::one();
+                Input {
+                    value: Value::known(value),
+                    limb_bit_len: LIMB_BIT_LEN,
+                    bit_len,
+                }
+            })
+            .collect();
+
+        let circuit = TestCircuit::<Fp> { inputs };
+        let public_inputs = vec![vec![]];
+        let prover = match MockProver::run(k, &circuit, public_inputs) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_range_keygen_without_witnesses() {
+        use crate::curves::pasta::EqAffine;
+        use crate::halo2::plonk::{keygen_pk, keygen_vk};
+        use crate::halo2::poly::commitment::ParamsProver;
+        use crate::halo2::poly::ipa::commitment::ParamsIPA;
+
+        const LIMB_BIT_LEN: usize = 8;
+        const OVERFLOW_BIT_LEN: usize = 3;
+        let k: u32 = (LIMB_BIT_LEN + 2) as u32;
+
+        let inputs = (2..20)
+            .map(|number_of_limbs| Input {
+                value: Value::unknown(),
+                limb_bit_len: LIMB_BIT_LEN,
+                bit_len: LIMB_BIT_LEN * number_of_limbs + OVERFLOW_BIT_LEN,
+            })
+            .collect();
+        let circuit = TestCircuit::<Fp> { inputs };
+
+        let params = ParamsIPA::<EqAffine>::new(k);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        keygen_pk(&params, vk, &circuit).unwrap();
+    }
+
+    impl<F: PrimeField> TestConfig<F> for TestCircuitConfig {
+        fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+            Ok(self.range_chip::<F>().load_table(layouter)?)
+        }
+    }
+
+    /// Range chip with composition lookups in the regular or in the narrow
+    /// layout
+    struct Composition<const NARROW: bool>;
+
+    impl<const NARROW: bool> Composition<NARROW> {
+        fn composition_bit_lens() -> Vec<usize> {
+            vec![1, 4, 8]
+        }
+
+        fn overflow_bit_lens() -> Vec<usize> {
+            (1..8).collect()
+        }
+    }
+
+    impl<F: PrimeField, const NARROW: bool> Configure<F> for Composition<NARROW> {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            if NARROW {
+                TestCircuitConfig::new_narrow(
+                    meta,
+                    Self::composition_bit_lens(),
+                    Self::overflow_bit_lens(),
+                )
+            } else {
+                TestCircuitConfig::new(
+                    meta,
+                    Self::composition_bit_lens(),
+                    Self::overflow_bit_lens(),
+                )
+            }
+        }
+    }
+
+    /// Range chip with a single composition width, so composition lookups
+    /// take a constant tag
+    struct SingleWidth;
+
+    impl<F: PrimeField> Configure<F> for SingleWidth {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(meta, vec![8], vec![3])
+        }
+    }
+
+    type Synthesize<F> =
+        Box<dyn Fn(&RangeChip<F>, &MainGate<F>, &mut RegionCtx<'_, F>) -> Result<(), Error>>;
+
+    const K: u32 = 10;
+
+    /// Runs given synthesis function with the chips of the test circuit that
+    /// is configured with `C`
+    fn run_in<C, S>(k: u32, synthesize: S) -> Result<MockProver<Fp>, Error>
+    where
+        C: Configure<Fp, Config = TestCircuitConfig>,
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        test_utils::run::<C, _>(k, move |config, ctx| {
+            synthesize(&config.range_chip(), &config.main_gate(), ctx)
+        })
+    }
+
+    fn verify_in<C, S>(k: u32, synthesize: S) -> Result<(), Vec<VerifyFailure>>
+    where
+        C: Configure<Fp, Config = TestCircuitConfig>,
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        test_utils::verify::<C, _>(k, move |config, ctx| {
+            synthesize(&config.range_chip(), &config.main_gate(), ctx)
+        })
+    }
+
+    fn run<S>(synthesize: S) -> Result<MockProver<Fp>, Error>
+    where
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        run_with::<false, _>(synthesize)
+    }
+
+    fn run_with<const NARROW: bool, S>(synthesize: S) -> Result<MockProver<Fp>, Error>
+    where
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        run_in::<Composition<NARROW>, _>(K, synthesize)
+    }
+
+    fn verify<S>(synthesize: S) -> Result<(), Vec<VerifyFailure>>
+    where
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        verify_in::<Composition<false>, _>(K, synthesize)
+    }
+
+    /// Expects either a satisfied circuit or the witness to be rejected at
+    /// synthesis time. Rejection only shows that an honest prover can't build
+    /// the witness, constraints are tested with [`decompose_forged`]
+    fn assert_synthesized<S>(synthesize: S, expect_ok: bool)
+    where
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        match run(synthesize) {
+            Ok(prover) => {
+                assert!(expect_ok, "witness is expected to be rejected");
+                assert_eq!(prover.verify(), Ok(()));
+            }
+            Err(e) => {
+                assert!(!expect_ok, "{:#?}", e);
+                assert!(matches!(e, Error::Synthesis), "{:#?}", e);
+            }
+        }
+    }
+
+    /// Decomposes already assigned value to `bit_len` bits bypassing the
+    /// witness check. Limbs are canonical except the top one that takes what
+    /// doesn't fit, so an out of range value is left to the lookups
+    fn decompose_forged(
+        range_chip: &RangeChip<Fp>,
+        ctx: &mut RegionCtx<'_, Fp>,
+        value: &AssignedValue<Fp>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    ) -> Result<(), Error> {
+        let number_of_limbs = (bit_len + limb_bit_len - 1) / limb_bit_len;
+        let top_base = power_of_two::<Fp>(limb_bit_len * (number_of_limbs - 1));
+        let limbs = value.value().map(|value| {
+            let mut limbs = decompose(*value, number_of_limbs, limb_bit_len);
+            let low = limbs[..number_of_limbs - 1]
+                .iter()
+                .rev()
+                .fold(Fp::ZERO, |acc, limb| {
+                    acc * power_of_two::<Fp>(limb_bit_len) + limb
+                });
+            limbs[number_of_limbs - 1] = (*value - low) * top_base.invert().unwrap();
+            limbs
+        });
+        let limbs = (0..number_of_limbs)
+            .map(|i| limbs.as_ref().map(|limbs| limbs[i]))
+            .collect();
+        let (composed, _) = range_chip.decompose_unchecked(ctx, limbs, limb_bit_len, bit_len)?;
+        ctx.constrain_equal(value.cell(), composed.cell())
+    }
+
+    #[test]
+    fn test_range_decompose_forged() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for bit_len in [1, 8, 35] {
+            for (value, expect_ok) in [
+                (power_of_two::<Fp>(bit_len) - Fp::one(), true),
+                (power_of_two::<Fp>(bit_len), false),
+                (-Fp::one(), false),
+            ] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(value))?;
+                    decompose_forged(range_chip, ctx, &value, LIMB_BIT_LEN, bit_len)
+                });
+                assert_eq!(result.is_ok(), expect_ok, "bit length: {bit_len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_lookup_on_each_limb() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 4 * LIMB_BIT_LEN;
+
+        // First row of the composition layout after moving the last limb to
+        // the first column for overflow check:
+        // | A   | B   | C   | D   | E      |
+        // | --- | --- | --- | --- | ------ |
+        // | l_3 | l_0 | l_1 | l_2 | result |
+        for position in 0..4 {
+            for (limb, expect_ok) in [(255u64, true), (256u64, false)] {
+                let result = verify(move |range_chip, _, ctx| {
+                    let limbs = (0..4)
+                        .map(|i| {
+                            let limb = if i == position { limb } else { 1 };
+                            Value::known(Fp::from(limb))
+                        })
+                        .collect();
+                    range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+                    Ok(())
+                });
+                assert_eq!(result.is_ok(), expect_ok, "limb position: {position}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_decomposition_copy() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 67;
+
+        for (delta, expect_ok) in [(0u64, true), (1u64, false)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let value = power_of_two::<Fp>(BIT_LEN) - Fp::from(3);
+                let (assigned, limbs) =
+                    range_chip.decompose(ctx, Value::known(value), LIMB_BIT_LEN, BIT_LEN)?;
+
+                // copy the integer and its limbs to other columns of the main gate
+                let copied = main_gate.assign_to_column(
+                    ctx,
+                    assigned.value().map(|value| *value + Fp::from(delta)),
+                    MainGateColumn::E,
+                )?;
+                ctx.constrain_equal(assigned.cell(), copied.cell())?;
+                for limb in limbs.iter() {
+                    let copied = main_gate.assign_to_column(
+                        ctx,
+                        limb.value().copied(),
+                        MainGateColumn::C,
+                    )?;
+                    ctx.constrain_equal(limb.cell(), copied.cell())?;
+                }
+
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
+    }
+
+    #[test]
+    fn test_range_number_of_limbs() {
+        for limb_bit_len in [4, 8] {
+            for number_of_limbs in [3, 4, 6] {
+                let bit_len = limb_bit_len * number_of_limbs;
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let value = power_of_two::<Fp>(bit_len) - Fp::one();
+                    let (assigned, limbs) =
+                        range_chip.decompose(ctx, Value::known(value), limb_bit_len, bit_len)?;
+                    assert_eq!(limbs.len(), number_of_limbs);
+
+                    let terms: Vec<Term<Fp>> = limbs
+                        .iter()
+                        .zip(range_chip.bases(limb_bit_len))
+                        .map(|(limb, base)| Term::Assigned(limb, *base))
+                        .collect();
+                    let composed = main_gate.compose(ctx, &terms[..], Fp::ZERO)?;
+                    main_gate.assert_equal(ctx, &assigned, &composed)
+                });
+                assert_eq!(result, Ok(()), "number of limbs: {number_of_limbs}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_chained_rows() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for number_of_rows in [2, 4] {
+            let bit_len = NUMBER_OF_LOOKUP_LIMBS * LIMB_BIT_LEN * number_of_rows;
+            let result = verify(move |range_chip, _, ctx| {
+                let value = power_of_two::<Fp>(bit_len) - Fp::one();
+                let offset = ctx.offset();
+                let (_, limbs) =
+                    range_chip.decompose(ctx, Value::known(value), LIMB_BIT_LEN, bit_len)?;
+                assert_eq!(limbs.len(), NUMBER_OF_LOOKUP_LIMBS * number_of_rows);
+                assert_eq!(ctx.offset() - offset, number_of_rows);
+                Ok(())
+            });
+            assert_eq!(result, Ok(()), "number of rows: {number_of_rows}");
+        }
+
+        // Composition rows with the accumulator carried in the last column:
+        // | A   | B   | C   | D   | E    |
+        // | --- | --- | --- | --- | ---- |
+        // | l_0 | l_1 | l_2 | l_3 | -r_0 | r_0 = value
+        // | l_4 | l_5 | l_6 | l_7 | -r_1 | r_1 = r_0 - sum(l_i * 2^(8i)), i < 4
+        // | ... | ... | ... | ... | ...  |
+        for number_of_rows in [2, 4] {
+            // Tamper with each intermediate accumulator or none of them
+            for tampered in 0..number_of_rows {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let number_of_limbs = number_of_rows * NUMBER_OF_LOOKUP_LIMBS;
+                    let value = power_of_two::<Fp>(number_of_limbs * LIMB_BIT_LEN) - Fp::from(3);
+                    let limbs = decompose(value, number_of_limbs, LIMB_BIT_LEN);
+                    let bases = &range_chip.bases(LIMB_BIT_LEN)[..number_of_limbs];
+                    let tag = Fp::from(range_chip.config.bit_len_tag[&LIMB_BIT_LEN] as u64);
+
+                    let mut remaining = value;
+                    let rows = limbs
+                        .chunks(NUMBER_OF_LOOKUP_LIMBS)
+                        .zip(bases.chunks(NUMBER_OF_LOOKUP_LIMBS));
+                    for (i, (limbs, bases)) in rows.enumerate() {
+                        let intermediate = if i != 0 && i == tampered {
+                            remaining + Fp::one()
+                        } else {
+                            remaining
+                        };
+                        let option = if i == number_of_rows - 1 {
+                            CombinationOptionCommon::OneLinerAdd
+                        } else {
+                            CombinationOptionCommon::CombineToNextAdd(Fp::one())
+                        };
+
+                        range_chip.config.s_composition.enable(ctx)?;
+                        ctx.assign_fixed(
+                            || "tag_composition",
+                            range_chip.config.tag_composition.unwrap(),
+                            tag,
+                        )?;
+                        let terms = limbs
+                            .iter()
+                            .zip(bases.iter())
+                            .map(|(limb, base)| Term::Unassigned(Value::known(*limb), *base))
+                            .chain(iter::once(Term::Unassigned(
+                                Value::known(intermediate),
+                                -Fp::one(),
+                            )));
+                        main_gate.apply(ctx, terms, Fp::ZERO, option.into())?;
+
+                        remaining -= limbs
+                            .iter()
+                            .zip(bases.iter())
+                            .fold(Fp::ZERO, |acc, (limb, base)| acc + *limb * base);
+                    }
+
+                    Ok(())
+                });
+                assert_eq!(
+                    result.is_ok(),
+                    tampered == 0,
+                    "number of rows: {number_of_rows}, tampered row: {tampered}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_overflow_boundary() {
+        const BIT_LEN: usize = 70;
+
+        for limb_bit_len in [4, 8] {
+            for (delta, expect_ok) in [(1u64, true), (0u64, false)] {
+                assert_synthesized(
+                    move |range_chip, _, ctx| {
+                        let value = power_of_two::<Fp>(BIT_LEN) - Fp::from(delta);
+                        range_chip.assign(ctx, Value::known(value), limb_bit_len, BIT_LEN)?;
+                        Ok(())
+                    },
+                    expect_ok,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_less_than_constant() {
+        use num_bigint::BigUint;
+
+        let bounds = [
+            (4, BigUint::from(1000u64)),
+            (4, BigUint::from(1024u64)),
+            (8, BigUint::from(1u64)),
+            (8, (BigUint::from(1u64) << 100) + 12345u64),
+        ];
+        for (limb_bit_len, bound) in bounds {
+            for (value, expect_ok) in [
+                (BigUint::from(0u64), true),
+                (&bound - 1u64, true),
+                (bound.clone(), false),
+                (&bound + 1u64, false),
+            ] {
+                let bound = bound.clone();
+                let value: Fp = big_to_fe(value);
+                assert_synthesized(
+                    move |range_chip, _, ctx| {
+                        range_chip.assign_less_than_constant(
+                            ctx,
+                            Value::known(value),
+                            limb_bit_len,
+                            &bound,
+                        )?;
+                        Ok(())
+                    },
+                    expect_ok,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_row_cost() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for bit_len in 1..=(3 * NUMBER_OF_LOOKUP_LIMBS * LIMB_BIT_LEN) {
+            let result = verify(move |range_chip, _, ctx| {
+                let number_of_limbs = (bit_len - 1) / LIMB_BIT_LEN + 1;
+                let offset = ctx.offset();
+                range_chip.assign(ctx, Value::known(Fp::one()), LIMB_BIT_LEN, bit_len)?;
+                assert_eq!(
+                    ctx.offset() - offset,
+                    (number_of_limbs - 1) / NUMBER_OF_LOOKUP_LIMBS + 1,
+                    "bit length: {bit_len}"
+                );
+                Ok(())
+            });
+            assert_eq!(result, Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_range_decompose_assigned() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 35;
+
+        for (delta, expect_ok) in [(1u64, true), (0u64, false)] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value = power_of_two::<Fp>(BIT_LEN) - Fp::from(delta);
+                    let assigned =
+                        main_gate.assign_to_column(ctx, Value::known(value), MainGateColumn::C)?;
+                    let limbs =
+                        range_chip.decompose_assigned(ctx, &assigned, LIMB_BIT_LEN, BIT_LEN)?;
+                    assert_eq!(limbs.len(), 5);
+                    Ok(())
+                },
+                expect_ok,
+            );
+        }
+    }
+
+    /// Test circuit that decomposes a value in one region and copies its
+    /// limbs to another region
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitCopy<F: PrimeField> {
+        input: Option<Input<F>>,
+        delta: F,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitCopy<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(
+                meta,
+                TestCircuit::<F>::composition_bit_lens(),
+                TestCircuit::<F>::overflow_bit_lens(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+            let main_gate = config.main_gate();
+            let input = self.input.as_ref().unwrap();
+
+            let limbs = layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    let (_, limbs) = range_chip.decompose(
+                        ctx,
+                        input.value,
+                        input.limb_bit_len,
+                        input.bit_len,
+                    )?;
+                    Ok(limbs)
+                },
+            )?;
+
+            layouter.assign_region(
+                || "region 1",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    for limb in limbs.iter() {
+                        let copied = main_gate.assign_to_column(
+                            ctx,
+                            limb.value().map(|value| *value + self.delta),
+                            MainGateColumn::D,
+                        )?;
+                        ctx.constrain_equal(limb.cell(), copied.cell())?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            range_chip.load_table(&mut layouter)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_copy_limbs_to_another_region() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 3 * LIMB_BIT_LEN + 3;
+
+        for (delta, expect_ok) in [(0u64, true), (1u64, false)] {
+            let input = Input {
+                value: Value::known(Fp::from(0x5a5a5a5)),
+                limb_bit_len: LIMB_BIT_LEN,
+                bit_len: BIT_LEN,
+            };
+            let circuit = TestCircuitCopy::<Fp> {
+                input: Some(input),
+                delta: Fp::from(delta),
+            };
+            let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+                Ok(prover) => prover,
+                Err(e) => panic!("{:#?}", e),
+            };
+            assert_eq!(prover.verify().is_ok(), expect_ok);
+        }
+    }
+
+    /// Test circuit that assigns all values in a single region
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitBatch<F: PrimeField> {
+        values: Vec<Value<F>>,
+        limb_bit_len: usize,
+        bit_len: usize,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitBatch<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                values: vec![Value::unknown(); self.values.len()],
+                limb_bit_len: self.limb_bit_len,
+                bit_len: self.bit_len,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(
+                meta,
+                TestCircuit::<F>::composition_bit_lens(),
+                TestCircuit::<F>::overflow_bit_lens(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+            let main_gate = config.main_gate();
+
+            let assigned = range_chip.range_values(
+                &mut layouter,
+                &self.values,
+                self.limb_bit_len,
+                self.bit_len,
+            )?;
+            assert_eq!(assigned.len(), self.values.len());
+
+            layouter.assign_region(
+                || "region 1",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    for (a_0, value) in assigned.iter().zip(self.values.iter()) {
+                        let a_1 = main_gate.assign_value(ctx, *value)?;
+                        main_gate.assert_equal(ctx, a_0, &a_1)?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            range_chip.load_table(&mut layouter)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_values_batch() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 3 * LIMB_BIT_LEN + 3;
+        const NUMBER_OF_VALUES: usize = 100;
+
+        for (bad, expect_ok) in [(None, true), (Some(NUMBER_OF_VALUES / 2), false)] {
+            let values = (0..NUMBER_OF_VALUES)
+                .map(|i| {
+                    let value = if Some(i) == bad {
+                        power_of_two::<Fp>(BIT_LEN)
+                    } else {
+                        Fp::from((i * 0x10101) as u64)
+                    };
+                    Value::known(value)
+                })
+                .collect();
+            let circuit = TestCircuitBatch::<Fp> {
+                values,
+                limb_bit_len: LIMB_BIT_LEN,
+                bit_len: BIT_LEN,
+            };
+            match MockProver::run(K, &circuit, vec![vec![]]) {
+                Ok(prover) => {
+                    assert!(expect_ok);
+                    assert_eq!(prover.verify(), Ok(()));
+                }
+                Err(e) => {
+                    assert!(!expect_ok);
+                    assert!(matches!(e, Error::Synthesis), "{:#?}", e);
+                }
+            };
+        }
+    }
+
+    /// Test circuit that exposes ranged values as public inputs
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitPublic<F: PrimeField> {
+        inputs: Vec<Input<F>>,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitPublic<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(
+                meta,
+                TestCircuit::<F>::composition_bit_lens(),
+                TestCircuit::<F>::overflow_bit_lens(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+
+            for (row, input) in self.inputs.iter().enumerate() {
+                let assigned = range_chip.range_value(
+                    &mut layouter,
+                    input.value,
+                    input.limb_bit_len,
+                    input.bit_len,
+                )?;
+                range_chip.expose_public(layouter.namespace(|| "public"), assigned, row)?;
+            }
+
+            range_chip.load_table(&mut layouter)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_expose_public() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        let values: Vec<Fp> = (1..4)
+            .map(|number_of_limbs| {
+                power_of_two::<Fp>(LIMB_BIT_LEN * number_of_limbs + 3) - Fp::one()
+            })
+            .collect();
+        let inputs = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Input {
+                value: Value::known(*value),
+                limb_bit_len: LIMB_BIT_LEN,
+                bit_len: LIMB_BIT_LEN * (i + 1) + 3,
+            })
+            .collect();
+        let circuit = TestCircuitPublic::<Fp> { inputs };
+
+        let prover = match MockProver::run(K, &circuit, vec![values.clone()]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+
+        let mut public_inputs = values;
+        public_inputs[1] += Fp::one();
+        let prover = match MockProver::run(K, &circuit, vec![public_inputs]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_range_reject_overflowing_witness() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for bit_len in [1, 8, 27, 64, 70] {
+            // Decomposition would silently drop the most significant bit
+            let value = power_of_two::<Fp>(bit_len);
+            let result = run(move |range_chip, _, ctx| {
+                range_chip.decompose(ctx, Value::known(value), LIMB_BIT_LEN, bit_len)?;
+                Ok(())
+            });
+            assert!(
+                matches!(result, Err(Error::Synthesis)),
+                "bit length: {bit_len}"
+            );
+
+            // Limbs assigned without the witness check are still rejected
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let number_of_limbs = (bit_len - 1) / LIMB_BIT_LEN + 1;
+                let limbs = decompose(value, number_of_limbs, LIMB_BIT_LEN)
+                    .into_iter()
+                    .map(Value::known)
+                    .collect();
+                let (assigned, _) =
+                    range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, bit_len)?;
+                let expected = main_gate.assign_value(ctx, Value::known(value))?;
+                main_gate.assert_equal(ctx, &assigned, &expected)
+            });
+            assert!(result.is_err(), "bit length: {bit_len}");
+        }
+    }
+
+    #[test]
+    fn test_range_decompose_big() {
+        use halo2wrong::utils::{compose, fe_to_big};
+        use num_bigint::{BigUint, RandomBits};
+        use num_traits::One;
+        use rand::Rng;
+        use rand_core::OsRng;
+
+        for (limb_bit_len, bit_len) in [(8, 256), (4, 256), (8, 260), (8, 512)] {
+            let value: BigUint = OsRng.sample(RandomBits::new(bit_len as u64));
+            let expected = value.clone();
+            let result = verify(move |range_chip, _, ctx| {
+                let limbs = range_chip.decompose_big(
+                    ctx,
+                    Value::known(value.clone()),
+                    limb_bit_len,
+                    bit_len,
+                )?;
+                assert_eq!(limbs.len(), (bit_len - 1) / limb_bit_len + 1);
+
+                let mut limb_values = vec![];
+                for limb in limbs.iter() {
+                    limb.value().map(|limb| limb_values.push(fe_to_big(*limb)));
+                }
+                assert_eq!(compose(limb_values, limb_bit_len), expected);
+                Ok(())
+            });
+            assert_eq!(result, Ok(()), "bit length: {bit_len}");
+
+            let value = BigUint::one() << bit_len;
+            let result = run(move |range_chip, _, ctx| {
+                range_chip.decompose_big(
+                    ctx,
+                    Value::known(value.clone()),
+                    limb_bit_len,
+                    bit_len,
+                )?;
+                Ok(())
+            });
+            assert!(matches!(result, Err(Error::Synthesis)));
+        }
+    }
+
+    #[test]
+    fn test_range_to_bits() {
+        const NUMBER_OF_BITS: usize = 64;
+
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let value = Fp::from(0xdead_beef_0123_4567);
+            let offset = ctx.offset();
+            let (assigned, bits) = range_chip.to_bits(ctx, Value::known(value), NUMBER_OF_BITS)?;
+            assert_eq!(
+                ctx.offset() - offset,
+                NUMBER_OF_BITS / NUMBER_OF_LOOKUP_LIMBS
+            );
+            assert_eq!(bits.len(), NUMBER_OF_BITS);
+
+            for (i, bit) in bits.iter().enumerate() {
+                let expected = (0xdead_beef_0123_4567u64 >> i) & 1;
+                let expected = main_gate.assign_constant(ctx, Fp::from(expected))?;
+                main_gate.assert_equal(ctx, bit, &expected)?;
+            }
+            let expected = main_gate.assign_value(ctx, Value::known(value))?;
+            main_gate.assert_equal(ctx, &assigned, &expected)
+        });
+        assert_eq!(result, Ok(()));
+
+        // Composition holds but a bit equal to 2 fails the 1 bit lookup
+        let result = verify(move |range_chip, _, ctx| {
+            let bits = [0, 2, 1, 1, 1, 0, 0, 1]
+                .into_iter()
+                .map(|bit| Value::known(Fp::from(bit)))
+                .collect();
+            range_chip.decompose_unchecked(ctx, bits, 1, 8)?;
+            Ok(())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_top_limb() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for overflow_bit_len in 1..LIMB_BIT_LEN {
+            // Five limbs so that the top limb lands on the second row
+            let bit_len = 4 * LIMB_BIT_LEN + overflow_bit_len;
+            for (delta, expect_ok) in [(1u64, true), (0u64, false)] {
+                let result = verify(move |range_chip, _, ctx| {
+                    let top = (1 << overflow_bit_len) - delta;
+                    let limbs = [255, 0, 255, 0, top]
+                        .into_iter()
+                        .map(|limb| Value::known(Fp::from(limb)))
+                        .collect();
+                    range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, bit_len)?;
+                    Ok(())
+                });
+                assert_eq!(
+                    result.is_ok(),
+                    expect_ok,
+                    "overflow bit length: {overflow_bit_len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_less_than() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        for bit_len in [8, 35, 130] {
+            let max = power_of_two::<Fp>(bit_len) - Fp::one();
+            for (a, b, expect_ok) in [
+                (Fp::ZERO, Fp::one(), true),
+                (max - Fp::one(), max, true),
+                (Fp::ZERO, max, true),
+                (max, max, false),
+                (Fp::ZERO, Fp::ZERO, false),
+                (max, Fp::ZERO, false),
             ] {
-                Self::configure_lookup_with_column_tag(
-                    meta,
-                    name,
-                    s_composition,
-                    tag,
-                    value,
-                    t_tag,
-                    t_value,
+                assert_synthesized(
+                    move |range_chip, _, ctx| {
+                        let (a, b) = range_chip.assign_less_than(
+                            ctx,
+                            Value::known(a),
+                            Value::known(b),
+                            LIMB_BIT_LEN,
+                            bit_len,
+                        )?;
+                        let diff =
+                            range_chip.assert_less_than(ctx, &a, &b, LIMB_BIT_LEN, bit_len)?;
+                        diff.value()
+                            .zip(a.value().zip(b.value()))
+                            .assert_if_known(|(diff, (a, b))| **diff == **b - **a - Fp::one());
+                        Ok(())
+                    },
+                    expect_ok,
+                );
+            }
+
+            // Forged differences get past the witness checks
+            for (a, b) in [(max, max), (Fp::ZERO, Fp::ZERO), (max, Fp::ZERO)] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let a = range_chip.assign(ctx, Value::known(a), LIMB_BIT_LEN, bit_len)?;
+                    let b = range_chip.assign(ctx, Value::known(b), LIMB_BIT_LEN, bit_len)?;
+                    let diff = main_gate.sub_with_constant(ctx, &b, &a, -Fp::one())?;
+                    decompose_forged(range_chip, ctx, &diff, LIMB_BIT_LEN, bit_len)
+                });
+                assert!(result.is_err(), "bit length: {bit_len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_greater_equal_and_interval() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 35;
+
+        for (a, b, expect_ok) in [(5u64, 5u64, true), (6, 5, true), (4, 5, false)] {
+            assert_synthesized(
+                move |range_chip, _, ctx| {
+                    let a =
+                        range_chip.assign(ctx, Value::known(Fp::from(a)), LIMB_BIT_LEN, BIT_LEN)?;
+                    let b =
+                        range_chip.assign(ctx, Value::known(Fp::from(b)), LIMB_BIT_LEN, BIT_LEN)?;
+                    range_chip.assert_greater_equal(ctx, &a, &b, LIMB_BIT_LEN, BIT_LEN)?;
+                    Ok(())
+                },
+                expect_ok,
+            );
+        }
+
+        // Forged difference gets past the witness checks
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let a = range_chip.assign(ctx, Value::known(Fp::from(4)), LIMB_BIT_LEN, BIT_LEN)?;
+            let b = range_chip.assign(ctx, Value::known(Fp::from(5)), LIMB_BIT_LEN, BIT_LEN)?;
+            let diff = main_gate.sub(ctx, &a, &b)?;
+            decompose_forged(range_chip, ctx, &diff, LIMB_BIT_LEN, BIT_LEN)
+        });
+        assert!(result.is_err());
+
+        // Lower end is inclusive and upper end is exclusive
+        const LO: u64 = 100;
+        const HI: u64 = 200;
+        for (value, expect_ok) in [
+            (LO - 1, false),
+            (LO, true),
+            (150, true),
+            (HI - 1, true),
+            (HI, false),
+        ] {
+            assert_synthesized(
+                move |range_chip, _, ctx| {
+                    let [value, lo, hi] = [value, LO, HI].map(|value| {
+                        range_chip
+                            .assign(ctx, Value::known(Fp::from(value)), LIMB_BIT_LEN, BIT_LEN)
+                            .unwrap()
+                    });
+                    let (lower, upper) = range_chip.assert_in_interval(
+                        ctx,
+                        &value,
+                        &lo,
+                        &hi,
+                        LIMB_BIT_LEN,
+                        BIT_LEN,
+                    )?;
+                    lower
+                        .value()
+                        .zip(upper.value())
+                        .assert_if_known(|(lower, upper)| {
+                            **lower + **upper == Fp::from(HI - LO - 1)
+                        });
+                    Ok(())
+                },
+                expect_ok,
+            );
+        }
+
+        // Forged difference at either end gets past the witness checks while
+        // the other difference is decomposed honestly
+        for (value, forge_lower) in [(LO - 1, true), (HI, false)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let [value, lo, hi] = [value, LO, HI].map(|value| {
+                    range_chip
+                        .assign(ctx, Value::known(Fp::from(value)), LIMB_BIT_LEN, BIT_LEN)
+                        .unwrap()
+                });
+                let lower = main_gate.sub(ctx, &value, &lo)?;
+                let upper = main_gate.sub_with_constant(ctx, &hi, &value, -Fp::one())?;
+                let (forged, honest) = if forge_lower {
+                    (lower, upper)
+                } else {
+                    (upper, lower)
+                };
+                range_chip.decompose_assigned(ctx, &honest, LIMB_BIT_LEN, BIT_LEN)?;
+                decompose_forged(range_chip, ctx, &forged, LIMB_BIT_LEN, BIT_LEN)
+            });
+            assert!(result.is_err(), "value: {value}");
+        }
+    }
+
+    #[test]
+    fn test_range_is_less_than() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 16;
+
+        let max = (1u64 << BIT_LEN) - 1;
+        for (a, b) in [
+            (0u64, 0u64),
+            (0, 1),
+            (1, 0),
+            (3, 5),
+            (5, 3),
+            (0x1234, 0x1234),
+            (0, max),
+            (max, 0),
+            (max - 1, max),
+            (max, max),
+        ] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let a_assigned = main_gate.assign_value(ctx, Value::known(Fp::from(a)))?;
+                let b_assigned = main_gate.assign_value(ctx, Value::known(Fp::from(b)))?;
+                let lt = range_chip.is_less_than(
+                    ctx,
+                    &a_assigned,
+                    &b_assigned,
+                    LIMB_BIT_LEN,
+                    BIT_LEN,
+                )?;
+                main_gate.assert_equal_to_constant(ctx, &lt, Fp::from((a < b) as u64))
+            });
+            assert_eq!(result, Ok(()), "a: {a}, b: {b}");
+        }
+
+        // Flip the top bit of `5 - 3 + 2^16` so that the low part carries
+        // into a limb that doesn't fit
+        let result = verify(|range_chip, main_gate, ctx| {
+            let a = main_gate.assign_value(ctx, Value::known(Fp::from(5)))?;
+            let b = main_gate.assign_value(ctx, Value::known(Fp::from(3)))?;
+            let limbs = vec![Value::known(Fp::from(2)), Value::known(Fp::from(0x100))];
+            let (forged_low, _) =
+                range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+            let forged_top = main_gate.assign_bit(ctx, Value::known(Fp::ZERO))?;
+            let base = power_of_two::<Fp>(BIT_LEN);
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&a, Fp::one()),
+                    Term::Assigned(&b, -Fp::one()),
+                    Term::Assigned(&forged_low, -Fp::one()),
+                    Term::Assigned(&forged_top, -base),
+                ],
+                base,
+            )
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_is_in_range() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 20;
+        const MAX_BIT_LEN: usize = 35;
+
+        for (value, expected) in [
+            (Fp::ZERO, true),
+            (power_of_two::<Fp>(BIT_LEN) - Fp::one(), true),
+            (power_of_two::<Fp>(BIT_LEN), false),
+            (power_of_two::<Fp>(MAX_BIT_LEN) - Fp::one(), false),
+        ] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let value = main_gate.assign_value(ctx, Value::known(value))?;
+                let cond =
+                    range_chip.is_in_range(ctx, &value, LIMB_BIT_LEN, BIT_LEN, MAX_BIT_LEN)?;
+                main_gate.assert_equal_to_constant(ctx, &cond, Fp::from(expected as u64))
+            });
+            assert_eq!(result, Ok(()));
+        }
+
+        // Try to forge the condition by placing an out of range value into the
+        // low part and set the high part to zero
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let value = power_of_two::<Fp>(BIT_LEN);
+            // `2^20 = 16 * 2^16` where 16 doesn't fit in the 4 bit overflow limb
+            let limbs = [0, 0, 16]
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect();
+            let (low, _) = range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+            let high = range_chip.assign(
+                ctx,
+                Value::known(Fp::ZERO),
+                LIMB_BIT_LEN,
+                MAX_BIT_LEN - BIT_LEN,
+            )?;
+            let value = main_gate.assign_value(ctx, Value::known(value))?;
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&low, Fp::one()),
+                    Term::Assigned(&high, power_of_two(BIT_LEN)),
+                    Term::Assigned(&value, -Fp::one()),
+                ],
+                Fp::ZERO,
+            )?;
+            let cond = main_gate.is_zero(ctx, &high)?;
+            main_gate.assert_one(ctx, &cond)
+        });
+        assert!(result.is_err());
+    }
+
+    /// Test circuit with two range chips of different limb sizes sharing the
+    /// main gate
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitTwoChips<F: PrimeField> {
+        inputs: Vec<Input<F>>,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitTwoChips<F> {
+        type Config = (RangeConfig, RangeConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config_0 =
+                RangeChip::<F>::configure(meta, &main_gate_config, vec![4], vec![2]);
+            let range_config_1 =
+                RangeChip::<F>::configure(meta, &main_gate_config, vec![8], vec![3]);
+            (range_config_0, range_config_1)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chips = [RangeChip::<F>::new(config.0), RangeChip::<F>::new(config.1)];
+
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    for input in self.inputs.iter() {
+                        let range_chip = range_chips
+                            .iter()
+                            .find(|range_chip| {
+                                range_chip
+                                    .config
+                                    .bit_len_tag
+                                    .contains_key(&input.limb_bit_len)
+                            })
+                            .unwrap();
+                        range_chip.assign(ctx, input.value, input.limb_bit_len, input.bit_len)?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            for range_chip in range_chips.iter() {
+                range_chip.load_table(&mut layouter)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_two_chips() {
+        let inputs = |delta: u64| {
+            [(4, 4 * 5 + 2), (8, 8 * 5 + 3)]
+                .into_iter()
+                .map(|(limb_bit_len, bit_len)| Input {
+                    value: Value::known(power_of_two::<Fp>(bit_len) - Fp::from(delta)),
+                    limb_bit_len,
+                    bit_len,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let circuit = TestCircuitTwoChips::<Fp> { inputs: inputs(1) };
+        let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = TestCircuitTwoChips::<Fp> { inputs: inputs(0) };
+        let result = MockProver::run(K, &circuit, vec![vec![]]);
+        assert!(matches!(result, Err(Error::Synthesis)));
+    }
+
+    #[test]
+    fn test_range_signed() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 16;
+
+        let half = power_of_two::<Fp>(BIT_LEN - 1);
+        for (value, sign) in [
+            (-half, Some(true)),
+            (-Fp::one(), Some(true)),
+            (Fp::ZERO, Some(false)),
+            (half - Fp::one(), Some(false)),
+            (half, None),
+            (-half - Fp::one(), None),
+        ] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let (shifted, cond) = range_chip.assign_signed(
+                        ctx,
+                        Value::known(value),
+                        LIMB_BIT_LEN,
+                        BIT_LEN,
+                    )?;
+                    let restored = main_gate.add_constant(ctx, &shifted, -half)?;
+                    let expected = main_gate.assign_value(ctx, Value::known(value))?;
+                    main_gate.assert_equal(ctx, &restored, &expected)?;
+                    main_gate.assert_equal_to_constant(ctx, &cond, Fp::from(sign.unwrap() as u64))
+                },
+                sign.is_some(),
+            );
+        }
+
+        // Forged high part of the shifted value gets past the witness checks
+        for value in [half, -half - Fp::one()] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let shifted = main_gate.assign_value(ctx, Value::known(value + half))?;
+                let (low, high) = split_at(value + half, BIT_LEN - 1);
+                let low = range_chip.assign(ctx, Value::known(low), LIMB_BIT_LEN, BIT_LEN - 1)?;
+                let high = main_gate.assign_value(ctx, Value::known(high))?;
+                decompose_forged(range_chip, ctx, &high, LIMB_BIT_LEN, 1)?;
+                main_gate.assert_zero_sum(
+                    ctx,
+                    &[
+                        Term::Assigned(&low, Fp::one()),
+                        Term::Assigned(&high, half),
+                        Term::Assigned(&shifted, -Fp::one()),
+                    ],
+                    Fp::ZERO,
+                )?;
+                main_gate.is_zero(ctx, &high)?;
+                Ok(())
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_range_merge_and_split() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 32;
+
+        // Split points that are not multiples of the limb size
+        for k in [1, 5, 8, 13, BIT_LEN - 3] {
+            for value in [0u64, 1 << k, (1 << k) - 1, 0xdead_beef] {
+                let (hi, lo) = (value >> k, value & ((1 << k) - 1));
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let assigned_hi = main_gate.assign_value(ctx, Value::known(Fp::from(hi)))?;
+                    let assigned_lo = main_gate.assign_value(ctx, Value::known(Fp::from(lo)))?;
+                    let merged = range_chip.merge(
+                        ctx,
+                        &assigned_hi,
+                        &assigned_lo,
+                        k,
+                        LIMB_BIT_LEN,
+                        BIT_LEN,
+                    )?;
+                    main_gate.assert_equal_to_constant(ctx, &merged, Fp::from(value))?;
+
+                    let (split_hi, split_lo) =
+                        range_chip.split(ctx, &merged, k, LIMB_BIT_LEN, BIT_LEN)?;
+                    main_gate.assert_equal(ctx, &split_hi, &assigned_hi)?;
+                    main_gate.assert_equal(ctx, &split_lo, &assigned_lo)
+                });
+                assert_eq!(result, Ok(()), "value: {value}, k: {k}");
+            }
+        }
+
+        // Low half that doesn't fit in `k` bits
+        const K: usize = 13;
+        assert_synthesized(
+            |range_chip, main_gate, ctx| {
+                let hi = main_gate.assign_value(ctx, Value::known(Fp::from(1)))?;
+                let lo = main_gate.assign_value(ctx, Value::known(Fp::from(1 << K)))?;
+                range_chip.merge(ctx, &hi, &lo, K, LIMB_BIT_LEN, BIT_LEN)?;
+                Ok(())
+            },
+            false,
+        );
+    }
+
+    #[test]
+    fn test_range_shr_const() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 32;
+
+        for value in [0u64, 1, 0x1234_5678, (1 << BIT_LEN) - 1] {
+            for k in [0, 1, 8, 12, BIT_LEN - 1, BIT_LEN] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                    let hi = range_chip.shr_const(ctx, &value, k, LIMB_BIT_LEN, BIT_LEN)?;
+                    main_gate.assert_equal_to_constant(ctx, &hi, Fp::from(value >> k))
+                });
+                assert_eq!(result, Ok(()), "value: {value}, k: {k}");
+            }
+        }
+
+        // Value doesn't fit in the bit length
+        for k in [0, 12, BIT_LEN] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value =
+                        main_gate.assign_value(ctx, Value::known(Fp::from(1 << BIT_LEN)))?;
+                    range_chip.shr_const(ctx, &value, k, LIMB_BIT_LEN, BIT_LEN)?;
+                    Ok(())
+                },
+                false,
+            );
+        }
+
+        // Forge `hi + 1` where `lo - 2^k` is carried into a limb that doesn't
+        // fit
+        const K: usize = 16;
+        let result = verify(|range_chip, main_gate, ctx| {
+            let (hi, lo) = (0x1234u64, 0x5678u64);
+            let value = main_gate.assign_value(ctx, Value::known(Fp::from((hi << K) + lo)))?;
+            let forged_hi = range_chip.assign(
+                ctx,
+                Value::known(Fp::from(hi + 1)),
+                LIMB_BIT_LEN,
+                BIT_LEN - K,
+            )?;
+            let limbs = vec![
+                Value::known(Fp::from(lo) - power_of_two::<Fp>(K)),
+                Value::known(Fp::ZERO),
+            ];
+            let (forged_lo, _) = range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, K)?;
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&forged_lo, Fp::one()),
+                    Term::Assigned(&forged_hi, power_of_two(K)),
+                    Term::Assigned(&value, -Fp::one()),
+                ],
+                Fp::ZERO,
+            )
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_mod_const() {
+        use num_bigint::BigUint;
+        use rand::Rng;
+        use rand_core::OsRng;
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 16;
+
+        for m in [1u64, 3, 1000, (1 << 16) + 1] {
+            let max = m << BIT_LEN;
+            let values = [0, m - 1, m, 5 * m, max - 1, OsRng.gen_range(0..max)];
+            for value in values {
+                assert_synthesized(
+                    move |range_chip, main_gate, ctx| {
+                        let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                        let (quotient, remainder) =
+                            range_chip.mod_const(ctx, &value, m, LIMB_BIT_LEN, BIT_LEN)?;
+                        main_gate.assert_equal_to_constant(ctx, &quotient, Fp::from(value / m))?;
+                        main_gate.assert_equal_to_constant(ctx, &remainder, Fp::from(value % m))
+                    },
+                    true,
+                );
+            }
+
+            // Quotient doesn't fit in the bit length
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(Fp::from(max)))?;
+                    range_chip.mod_const(ctx, &value, m, LIMB_BIT_LEN, BIT_LEN)?;
+                    Ok(())
+                },
+                false,
+            );
+
+            // Forged quotient gets past the witness checks
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let value = main_gate.assign_value(ctx, Value::known(Fp::from(max)))?;
+                let quotient = main_gate.assign_value(ctx, Value::known(Fp::from(1 << BIT_LEN)))?;
+                decompose_forged(range_chip, ctx, &quotient, LIMB_BIT_LEN, BIT_LEN)?;
+                let remainder = main_gate.assign_constant(ctx, Fp::ZERO)?;
+                range_chip.assert_bounded_by_constant(
+                    ctx,
+                    &remainder,
+                    LIMB_BIT_LEN,
+                    &BigUint::from(m),
+                )?;
+                main_gate.assert_zero_sum(
+                    ctx,
+                    &[
+                        Term::Assigned(&value, Fp::one()),
+                        Term::Assigned(&quotient, -Fp::from(m)),
+                        Term::Assigned(&remainder, -Fp::one()),
+                    ],
+                    Fp::ZERO,
                 )
+            });
+            assert!(result.is_err(), "m: {m}");
+        }
+
+        // Remainder that is not reduced, `5 * m = 4 * m + m`, where the single
+        // limb bound check is forged to get past the witness checks
+        const M: u64 = 3;
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let value = main_gate.assign_value(ctx, Value::known(Fp::from(5 * M)))?;
+            let quotient =
+                range_chip.assign(ctx, Value::known(Fp::from(4)), LIMB_BIT_LEN, BIT_LEN)?;
+            let remainder = main_gate.assign_value(ctx, Value::known(Fp::from(M)))?;
+            let limbs =
+                range_chip.decompose_assigned(ctx, &remainder, LIMB_BIT_LEN, LIMB_BIT_LEN)?;
+            let max = Fp::from(M - 1);
+            let diff = main_gate.assign_value(ctx, Value::known(max - Fp::from(M)))?;
+            decompose_forged(range_chip, ctx, &diff, LIMB_BIT_LEN, LIMB_BIT_LEN)?;
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&limbs[0], Fp::one()),
+                    Term::Assigned(&diff, Fp::one()),
+                ],
+                -max,
+            )?;
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&value, Fp::one()),
+                    Term::Assigned(&quotient, -Fp::from(M)),
+                    Term::Assigned(&remainder, -Fp::one()),
+                ],
+                Fp::ZERO,
+            )
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_abs() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 16;
+
+        let max = power_of_two::<Fp>(BIT_LEN);
+        for (value, expected) in [
+            (Fp::ZERO, Some((Fp::ZERO, false))),
+            (Fp::one(), Some((Fp::one(), false))),
+            (-Fp::one(), Some((Fp::one(), true))),
+            (max - Fp::one(), Some((max - Fp::one(), false))),
+            (-max + Fp::one(), Some((max - Fp::one(), true))),
+            // Most negative value has no absolute value in range
+            (-max, None),
+            (max, None),
+        ] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(value))?;
+                    let (abs, sign) = range_chip.abs(ctx, &value, LIMB_BIT_LEN, BIT_LEN)?;
+                    let (expected_abs, expected_sign) = expected.unwrap();
+                    main_gate.assert_equal_to_constant(ctx, &abs, expected_abs)?;
+                    main_gate.assert_equal_to_constant(ctx, &sign, Fp::from(expected_sign as u64))
+                },
+                expected.is_some(),
+            );
+        }
+
+        // Same constraints as `abs` where the absolute value is forged to get
+        // past the witness checks
+        fn forged(value: Fp, abs: Fp, sign: bool) -> Synthesize<Fp> {
+            Box::new(move |range_chip, main_gate, ctx| {
+                let value = main_gate.assign_value(ctx, Value::known(value))?;
+                let abs = main_gate.assign_value(ctx, Value::known(abs))?;
+                decompose_forged(range_chip, ctx, &abs, LIMB_BIT_LEN, BIT_LEN)?;
+                let sign = main_gate.assign_bit(ctx, Value::known(Fp::from(sign as u64)))?;
+                main_gate.apply(
+                    ctx,
+                    [
+                        Term::assigned_to_mul(&sign),
+                        Term::Assigned(&abs, -Fp::one()),
+                        Term::assigned_to_mul(&sign),
+                        Term::assigned_to_mul(&abs),
+                        Term::assigned_to_add(&value),
+                    ],
+                    Fp::ZERO,
+                    CombinationOption::OneLinerDoubleMul(Fp::one()),
+                )?;
+                let is_zero = main_gate.is_zero(ctx, &abs)?;
+                main_gate.nand(ctx, &sign, &is_zero)
+            })
+        }
+
+        assert_eq!(verify(forged(-Fp::one(), Fp::one(), true)), Ok(()));
+        // Absolute value of the extremes doesn't fit in the bit length
+        assert!(verify(forged(-max, max, true)).is_err());
+        assert!(verify(forged(max, max, false)).is_err());
+        // Negative zero is in range and is rejected only by the sign check
+        assert!(verify(forged(Fp::ZERO, Fp::ZERO, true)).is_err());
+    }
+
+    #[test]
+    fn test_range_padding_limb() {
+        const LIMB_BIT_LEN: usize = 4;
+        const BIT_LEN: usize = 3 * LIMB_BIT_LEN;
+
+        let result = verify(move |range_chip, _, ctx| {
+            let offset = ctx.offset();
+            let (_, limbs) =
+                range_chip.decompose(ctx, Value::known(Fp::from(0xabc)), LIMB_BIT_LEN, BIT_LEN)?;
+            assert_eq!(limbs.len(), 3);
+            assert_eq!(ctx.offset() - offset, 1);
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+
+        // Padding cell is placed with zero base so a limb smuggled into it
+        // can't contribute to the composed value:
+        // | A   | B   | C   | D       | E      |
+        // | --- | --- | --- | ------- | ------ |
+        // | l_2 | l_0 | l_1 | padding | -value |
+        for (padding, expect_ok) in [(0u64, true), (1u64, false)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let value = Fp::from(0xabc);
+                let claimed = value + Fp::from(padding) * power_of_two::<Fp>(BIT_LEN);
+                let tag = Fp::from(range_chip.config.bit_len_tag[&LIMB_BIT_LEN] as u64);
+                let bases = range_chip.bases(LIMB_BIT_LEN);
+
+                range_chip.config.s_composition.enable(ctx)?;
+                ctx.assign_fixed(
+                    || "tag_composition",
+                    range_chip.config.tag_composition.unwrap(),
+                    tag,
+                )?;
+                let terms = [
+                    Term::Unassigned(Value::known(Fp::from(0xa)), bases[2]),
+                    Term::Unassigned(Value::known(Fp::from(0xc)), bases[0]),
+                    Term::Unassigned(Value::known(Fp::from(0xb)), bases[1]),
+                    Term::Unassigned(Value::known(Fp::from(padding)), Fp::ZERO),
+                    Term::Unassigned(Value::known(claimed), -Fp::one()),
+                ];
+                main_gate.apply(
+                    ctx,
+                    terms,
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
+    }
+
+    #[test]
+    fn test_range_interleaved_with_other_rows() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let mut sum = main_gate.assign_constant(ctx, Fp::ZERO)?;
+            for (i, bit_len) in [20, 64, 131].into_iter().enumerate() {
+                // Unrelated rows in between decompositions
+                let scratch = main_gate.assign_to_column(
+                    ctx,
+                    Value::known(Fp::from(i as u64)),
+                    MainGateColumn::B,
+                )?;
+                main_gate.assert_equal_to_constant(ctx, &scratch, Fp::from(i as u64))?;
+
+                let value = power_of_two::<Fp>(bit_len) - Fp::one();
+                let offset = ctx.offset();
+                let assigned =
+                    range_chip.assign(ctx, Value::known(value), LIMB_BIT_LEN, bit_len)?;
+                let number_of_limbs = (bit_len - 1) / LIMB_BIT_LEN + 1;
+                assert_eq!(
+                    ctx.offset(),
+                    offset + (number_of_limbs - 1) / NUMBER_OF_LOOKUP_LIMBS + 1
+                );
+
+                sum = main_gate.add(ctx, &sum, &assigned)?;
+            }
+
+            let expected = [20, 64, 131].into_iter().fold(Fp::ZERO, |acc, bit_len| {
+                acc + power_of_two::<Fp>(bit_len) - Fp::one()
+            });
+            main_gate.assert_equal_to_constant(ctx, &sum, expected)
+        });
+        assert_eq!(result, Ok(()));
+    }
+
+    /// Test circuit with a range chip that reuses table of another one
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitSharedTable<F: PrimeField> {
+        inputs: Vec<Input<F>>,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitSharedTable<F> {
+        type Config = (RangeConfig, RangeConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config_0 =
+                RangeChip::<F>::configure(meta, &main_gate_config, vec![4, 8], vec![2, 3]);
+            let range_config_1 = RangeChip::<F>::configure_with_table(
+                meta,
+                &main_gate_config,
+                vec![8],
+                vec![3],
+                &range_config_0,
+            );
+            (range_config_0, range_config_1)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chips = [RangeChip::<F>::new(config.0), RangeChip::<F>::new(config.1)];
+
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    for (input, range_chip) in self.inputs.iter().zip(range_chips.iter().cycle()) {
+                        range_chip.assign(ctx, input.value, input.limb_bit_len, input.bit_len)?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            // Second load is a no-op for the chip sharing the table
+            for range_chip in range_chips.iter().rev() {
+                range_chip.load_table(&mut layouter)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_range_shared_table() {
+        let inputs = [
+            (4, 4 * 5 + 2),
+            (8, 8 * 5 + 3),
+            (8, 8 * 3 + 3),
+            (8, 8 * 2 + 3),
+        ]
+        .into_iter()
+        .map(|(limb_bit_len, bit_len)| Input {
+            value: Value::known(power_of_two::<Fp>(bit_len) - Fp::one()),
+            limb_bit_len,
+            bit_len,
+        })
+        .collect();
+        let circuit = TestCircuitSharedTable::<Fp> { inputs };
+        let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Both chips look up the same table that is loaded once by its owner
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let (range_config_0, range_config_1) = TestCircuitSharedTable::<Fp>::configure(&mut meta);
+        assert_eq!(range_config_0.table(), range_config_1.table());
+        assert_eq!(range_config_0.table_len(), range_config_1.table_len());
+
+        // Circuit with both chips fits in the size that the owner table needs
+        let k = range_config_0.min_k(meta.blinding_factors(), 0);
+        assert!(k < K);
+        let prover = match MockProver::run(k, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Test circuit that loads the table twice, either from the same chip or
+    /// from two chips built from the same owning config
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitLoadTwice<F: PrimeField> {
+        value: Value<F>,
+        separate_chips: bool,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitLoadTwice<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(meta, vec![8], vec![3])
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    range_chip.assign(ctx, self.value, 8, 11)?;
+                    Ok(())
+                },
+            )?;
+
+            assert_eq!(range_chip.check_loaded(), Err(RangeError::TableNotLoaded));
+            range_chip.load_table(&mut layouter)?;
+            assert_eq!(range_chip.check_loaded(), Ok(()));
+            if self.separate_chips {
+                let other = config.range_chip();
+                assert_eq!(other.check_loaded(), Err(RangeError::TableNotLoaded));
+                other.load_table(&mut layouter)?;
+            } else {
+                range_chip.clone().load_table(&mut layouter)?;
+                range_chip.load_table(&mut layouter)?;
             }
-            Some(tag)
+
+            Ok(())
+        }
+    }
+
+    /// Test circuit that ranges a value and never loads the table. Keeps the
+    /// error of finalizing the range chip if it is finalized
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitWithoutTable<F: PrimeField> {
+        value: Value<F>,
+        finalize: bool,
+        error: Rc<Cell<Option<RangeError>>>,
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitWithoutTable<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+                finalize: self.finalize,
+                error: self.error.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(meta, vec![8], vec![3])
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let range_chip = config.range_chip();
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+                    range_chip.assign(ctx, self.value, 8, 11)?;
+                    Ok(())
+                },
+            )?;
+            if self.finalize {
+                range_chip.finalize().map_err(|e| {
+                    self.error.set(Some(e));
+                    e
+                })?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Test circuit that only loads the table of [`TestCircuit`] and keeps
+    /// the error of the range chip
+    #[derive(Default, Clone, Debug)]
+    struct TestCircuitLoadTable {
+        error: Rc<Cell<Option<RangeError>>>,
+    }
+
+    impl TestCircuitLoadTable {
+        /// Runs the circuit of size `2^k` and returns the load error
+        fn load_error(k: u32) -> Option<RangeError> {
+            let circuit = Self::default();
+            let result = MockProver::<Fp>::run(k, &circuit, vec![vec![]]);
+            assert_eq!(result.is_err(), circuit.error.get().is_some());
+            circuit.error.get()
+        }
+    }
+
+    impl<F: PrimeField> Circuit<F> for TestCircuitLoadTable {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuit::<F>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config
+                .range_chip::<F>()
+                .load_table(&mut layouter)
+                .map_err(|e| {
+                    self.error.set(Some(e));
+                    e.into()
+                })
+        }
+    }
+
+    #[test]
+    fn test_range_table_not_loaded() {
+        let circuit = TestCircuitWithoutTable::<Fp> {
+            value: Value::known(Fp::from(0x7ff)),
+            ..Default::default()
+        };
+        let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        let failures = prover.verify().unwrap_err();
+        assert!(failures.iter().all(|failure| {
+            matches!(failure, VerifyFailure::Lookup { .. })
+                && ["composition", "overflow"]
+                    .iter()
+                    .any(|name| failure.to_string().contains(name))
+        }));
+
+        // Finalized chip reports the forgotten load at synthesis time in debug
+        // builds
+        let circuit = TestCircuitWithoutTable::<Fp> {
+            value: Value::known(Fp::from(0x7ff)),
+            finalize: true,
+            ..Default::default()
+        };
+        let result = MockProver::run(K, &circuit, vec![vec![]]);
+        if cfg!(debug_assertions) {
+            assert!(matches!(result, Err(Error::Synthesis)));
+            let error = circuit.error.get().unwrap();
+            assert_eq!(error, RangeError::TableNotLoaded);
+            assert_eq!(error.to_string(), "range table is not loaded");
         } else {
-            for (name, value) in [
-                ("composition_a", a),
-                ("composition_b", b),
-                ("composition_c", c),
-                ("composition_d", d),
+            assert!(result.is_ok());
+            assert_eq!(circuit.error.get(), None);
+        }
+    }
+
+    #[test]
+    fn test_range_table_values() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = Membership::<false>::configure(&mut meta).range_config;
+        let values: Vec<(Fp, Fp)> = config.table_values().collect();
+        assert_eq!(values.len(), config.table_len());
+        assert_eq!(values[0], (Fp::ZERO, Fp::ZERO));
+
+        // Each bit length covers exactly `[0, 2^bit_len)` under its tag
+        for (bit_len, tag) in config.bit_len_tag.iter() {
+            let tag = Fp::from(*tag as u64);
+            let tagged: Vec<Fp> = values
+                .iter()
+                .filter(|(row_tag, _)| *row_tag == tag)
+                .map(|(_, value)| *value)
+                .collect();
+            let expected: Vec<Fp> = (0..1u64 << bit_len).map(Fp::from).collect();
+            assert_eq!(tagged, expected, "bit length: {bit_len}");
+        }
+
+        // Bounds cover `[0, bound)` under their tags
+        for bound in Membership::<false>::bounds() {
+            let tag = Fp::from(config.bound_tag(bound).unwrap() as u64);
+            let count = values.iter().filter(|(row_tag, _)| *row_tag == tag).count();
+            assert_eq!(count as u64, bound);
+        }
+    }
+
+    /// Range chip with a table that spans a few chunks of assignment
+    struct LargeTable;
+
+    impl<F: PrimeField> Configure<F> for LargeTable {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(meta, vec![13], vec![3])
+        }
+    }
+
+    #[test]
+    fn test_range_assigned_table() {
+        use crate::halo2::dev::CellValue;
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = LargeTable::configure(&mut meta).range_config;
+        let (tags, values): (Vec<_>, Vec<_>) = config
+            .table_values::<Fp>()
+            .map(|(tag, value)| (CellValue::Assigned(tag), CellValue::Assigned(value)))
+            .unzip();
+        assert!(values.len() > 2 * TABLE_CHUNK_LEN);
+
+        // Chunked assignment leaves the rows in the order they are generated
+        let k = config.min_k(meta.blinding_factors(), 0);
+        let prover = run_in::<LargeTable, _>(k, |_, _, _| Ok(())).unwrap();
+        let column_of = |expected: &[CellValue<Fp>]| {
+            prover
+                .fixed()
+                .iter()
+                .position(|column| column[..expected.len()] == *expected)
+        };
+        let tag_column = column_of(&tags).expect("tag column is expected to be assigned");
+        let value_column = column_of(&values).expect("value column is expected to be assigned");
+        assert_ne!(tag_column, value_column);
+    }
+
+    #[test]
+    fn test_range_padding_rows() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 2 * LIMB_BIT_LEN;
+
+        // Padding rows between decompositions don't change the outcome
+        for pad in [false, true] {
+            for (limb, expect_ok) in [(255u64, true), (256u64, false)] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let value = Value::known(Fp::from(0xabcd));
+                    let a = range_chip.assign(ctx, value, LIMB_BIT_LEN, BIT_LEN)?;
+                    if pad {
+                        range_chip.no_operation(ctx)?;
+                        range_chip.assign_zero_row(ctx)?;
+                    }
+                    let limbs = vec![Value::known(Fp::from(limb)), Value::known(Fp::ONE)];
+                    let (b, _) =
+                        range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+                    if pad {
+                        range_chip.assign_zero_row(ctx)?;
+                    }
+                    main_gate.add(ctx, &a, &b)?;
+                    Ok(())
+                });
+                assert_eq!(result.is_ok(), expect_ok, "pad: {pad}, limb: {limb}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_load_table_twice() {
+        let circuit = TestCircuitLoadTwice::<Fp> {
+            value: Value::known(Fp::from(0x7ff)),
+            separate_chips: false,
+        };
+        let prover = match MockProver::run(K, &circuit, vec![vec![]]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Chips built separately from one config don't share the loaded state
+        // and the second assignment of the table columns is rejected
+        let circuit = TestCircuitLoadTwice::<Fp> {
+            value: Value::known(Fp::from(0x7ff)),
+            separate_chips: true,
+        };
+        assert!(MockProver::run(K, &circuit, vec![vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_range_errors() {
+        let check = |value: Value<Fp>, bit_len: usize| RangeChip::check_bit_len(value, bit_len, 3);
+        assert_eq!(check(Value::known(Fp::from(0xff)), 8), Ok(()));
+        assert_eq!(check(Value::unknown(), 8), Ok(()));
+
+        let err = check(Value::known(Fp::from(0x100)), 8).unwrap_err();
+        assert_eq!(
+            err,
+            RangeError::ValueTooLarge {
+                bits: 9,
+                bit_len: 8,
+                offset: 3
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "value of 9 bits doesn't fit in 8 bits at offset 3"
+        );
+        assert!(matches!(Error::from(err), Error::Synthesis));
+
+        // Negative values take the full width
+        assert_eq!(
+            check(Value::known(-Fp::one()), 64),
+            Err(RangeError::ValueTooLarge {
+                bits: Fp::NUM_BITS as usize,
+                bit_len: 64,
+                offset: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_chip_is_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RangeChip<Fp>>();
+    }
+
+    /// Packed range chip of 4 bit limbs
+    struct Packed;
+
+    impl<F: PrimeField> Configure<F> for Packed {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config = RangeChip::<F>::configure_packed(meta, &main_gate_config, 4);
+            TestCircuitConfig { range_config }
+        }
+    }
+
+    #[test]
+    fn test_range_packed_layout() {
+        const LIMB_BIT_LEN: usize = 4;
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = Packed::configure(&mut meta);
+        assert_eq!(meta.lookups().len(), 2);
+        let cost = range_config.cost(1, LIMB_BIT_LEN, 24);
+        assert_eq!(cost.lookup_arguments, 2);
+        assert_eq!(cost.rows, 2);
+        assert_eq!(cost.table_rows, 1 + (1 << (2 * LIMB_BIT_LEN)));
+
+        let run_packed = |synthesize: Synthesize<Fp>| run_in::<Packed, _>(K, synthesize);
+
+        for bit_len in [8, 16, 24, 32] {
+            for value in [0u64, 1, (1 << bit_len) - 1] {
+                let prover = run_packed(Box::new(move |range_chip, main_gate, ctx| {
+                    let value = Value::known(Fp::from(value));
+                    let (assigned, limbs) =
+                        range_chip.decompose(ctx, value, LIMB_BIT_LEN, bit_len)?;
+                    assert_eq!(limbs.len(), bit_len / LIMB_BIT_LEN);
+                    let expected = main_gate.assign_value(ctx, value)?;
+                    main_gate.assert_equal(ctx, &assigned, &expected)
+                }))
+                .unwrap();
+                assert_eq!(
+                    prover.verify(),
+                    Ok(()),
+                    "value: {value}, bit length: {bit_len}"
+                );
+            }
+        }
+
+        // `0x100` with a `b` limb that doesn't fit, so the packed pair doesn't
+        // fit either
+        let prover = run_packed(Box::new(|range_chip, main_gate, ctx| {
+            let limbs = [0x0u64, 0x10, 0x0, 0x0]
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect();
+            let (assigned, _) = range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, 16)?;
+            main_gate.assert_equal_to_constant(ctx, &assigned, Fp::from(0x100))
+        }))
+        .unwrap();
+        assert!(prover.verify().is_err());
+
+        // `a = 0x20` doesn't fit in a limb but the pair `0x20 + 0x0 * 2^4` is
+        // the same as the honest `0x0 + 0x2 * 2^4`, so single limbs are not
+        // ranged
+        let prover = run_packed(Box::new(|range_chip, main_gate, ctx| {
+            let limbs = [0x20u64, 0x0, 0x0, 0x0]
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect();
+            let (assigned, limbs) = range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, 16)?;
+            main_gate.assert_equal_to_constant(ctx, &limbs[0], Fp::from(0x20))?;
+            main_gate.assert_equal_to_constant(ctx, &assigned, Fp::from(0x20))
+        }))
+        .unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Range chip with eight 8 bit limbs per row
+    struct Wide;
+
+    impl<F: PrimeField> Configure<F> for Wide {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config =
+                RangeChip::<F>::configure_wide(meta, &main_gate_config, 8, (1..8).collect());
+            TestCircuitConfig { range_config }
+        }
+    }
+
+    /// Range chip with four 8 bit limbs per row to compare with [`Wide`]
+    struct Regular;
+
+    impl<F: PrimeField> Configure<F> for Regular {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            TestCircuitConfig::new(meta, vec![8], (1..8).collect())
+        }
+    }
+
+    #[test]
+    fn test_range_wide_layout() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = Fp::NUM_BITS as usize;
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = Wide::configure(&mut meta);
+        assert_eq!(meta.num_advice_columns(), 9);
+        assert_eq!(meta.lookups().len(), 2 * NUMBER_OF_LOOKUP_LIMBS + 1);
+        let wide = range_config.cost(1, LIMB_BIT_LEN, BIT_LEN);
+        assert_eq!(wide.lookup_arguments, meta.lookups().len());
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = Regular::configure(&mut meta);
+        let regular = range_config.cost(1, LIMB_BIT_LEN, BIT_LEN);
+        assert_eq!(regular.lookup_arguments, meta.lookups().len());
+
+        // Full width value takes half the rows in exchange for columns
+        assert_eq!((regular.rows, wide.rows), (8, 4));
+        assert_eq!(regular.table_rows, wide.table_rows);
+
+        // Honest decompositions pass in both layouts and rows in the region
+        // follow the cost
+        for bit_len in [1, 8, 63, 64, 65, 128, BIT_LEN] {
+            let value = if bit_len < BIT_LEN {
+                power_of_two::<Fp>(bit_len) - Fp::one()
+            } else {
+                -Fp::one()
+            };
+            let range_check = move |range_chip: &RangeChip<Fp>,
+                                    main_gate: &MainGate<Fp>,
+                                    ctx: &mut RegionCtx<'_, Fp>,
+                                    rows: &Cell<usize>|
+                  -> Result<(), Error> {
+                let offset = ctx.offset();
+                let assigned = range_chip.range_check(ctx, Value::known(value), bit_len)?;
+                rows.set(ctx.offset() - offset);
+                assert_eq!(rows.get(), range_chip.number_of_rows(LIMB_BIT_LEN, bit_len));
+                main_gate.assert_equal_to_constant(ctx, &assigned, value)
+            };
+            let (wide, regular) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+            let rows = wide.clone();
+            let result = verify_in::<Wide, _>(K, move |range_chip, main_gate, ctx| {
+                range_check(range_chip, main_gate, ctx, &rows)
+            });
+            assert_eq!(result, Ok(()), "bit length: {bit_len}");
+            let rows = regular.clone();
+            let result = verify_in::<Regular, _>(K, move |range_chip, main_gate, ctx| {
+                range_check(range_chip, main_gate, ctx, &rows)
+            });
+            assert_eq!(result, Ok(()), "bit length: {bit_len}");
+            assert_eq!(wide.get(), (regular.get() + 1) / 2, "bit length: {bit_len}");
+        }
+
+        // Out of range top limb is caught by the overflow lookup in the first
+        // column of the last row
+        for bit_len in [8, 71, 128] {
+            for (value, expect_ok) in [
+                (power_of_two::<Fp>(bit_len) - Fp::one(), true),
+                (power_of_two::<Fp>(bit_len), false),
             ] {
-                Self::configure_lookup_with_constant_tag(
-                    meta,
-                    name,
-                    s_composition,
-                    bit_len_tag[&composition_bit_lens[0]],
-                    value,
-                    t_tag,
-                    t_value,
-                )
+                let result = verify_in::<Wide, _>(K, move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(value))?;
+                    decompose_forged(range_chip, ctx, &value, LIMB_BIT_LEN, bit_len)
+                });
+                assert_eq!(result.is_ok(), expect_ok, "bit length: {bit_len}");
+            }
+        }
+
+        // Limb in each of the wide columns is looked up too. `0x100` at
+        // position `i` composes the same value as `1` at `i + 1`
+        for position in NUMBER_OF_LOOKUP_LIMBS..2 * NUMBER_OF_LOOKUP_LIMBS {
+            for (limb, expect_ok) in [(0xffu64, true), (0x100, false)] {
+                let result = verify_in::<Wide, _>(K, move |range_chip, main_gate, ctx| {
+                    let mut limbs = vec![Value::known(Fp::ZERO); 16];
+                    limbs[position] = Value::known(Fp::from(limb));
+                    let (assigned, _) =
+                        range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, 128)?;
+                    let expected = Fp::from(limb) * power_of_two::<Fp>(LIMB_BIT_LEN * position);
+                    main_gate.assert_equal_to_constant(ctx, &assigned, expected)
+                });
+                assert_eq!(result.is_ok(), expect_ok, "position: {position}");
+            }
+        }
+    }
+
+    /// Range chip of [`Composition`] widths with lookups enabled by fixed
+    /// columns
+    struct FixedEnable;
+
+    impl<F: PrimeField> Configure<F> for FixedEnable {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config = RangeChip::<F>::configure_with_fixed_enable(
+                meta,
+                &main_gate_config,
+                Composition::<false>::composition_bit_lens(),
+                Composition::<false>::overflow_bit_lens(),
+            );
+            TestCircuitConfig { range_config }
+        }
+    }
+
+    #[test]
+    fn test_range_fixed_enable() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = FixedEnable::configure(&mut meta);
+        assert!(matches!(
+            range_config.composition_selector(),
+            LookupEnable::Fixed(_)
+        ));
+        assert!(matches!(
+            range_config.overflow_selector(),
+            Some(LookupEnable::Fixed(_))
+        ));
+        let fixed = (
+            meta.degree(),
+            meta.lookups().len(),
+            meta.num_selectors(),
+            meta.num_fixed_columns(),
+        );
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = Composition::<false>::configure(&mut meta);
+        assert!(matches!(
+            range_config.composition_selector(),
+            LookupEnable::Selector(_)
+        ));
+        let selector = (
+            meta.degree(),
+            meta.lookups().len(),
+            meta.num_selectors(),
+            meta.num_fixed_columns(),
+        );
+
+        // Both selectors are traded for a fixed column each and the degree
+        // stays the same
+        assert_eq!(fixed.0, selector.0);
+        assert_eq!(fixed.1, selector.1);
+        assert_eq!(fixed.2 + 2, selector.2);
+        assert_eq!(fixed.3, selector.3 + 2);
+
+        for limb_bit_len in [1, 4, 8] {
+            for bit_len in [1, 8, 35] {
+                for value in [
+                    power_of_two::<Fp>(bit_len) - Fp::one(),
+                    power_of_two::<Fp>(bit_len),
+                    -Fp::one(),
+                ] {
+                    let forged = move |range_chip: &RangeChip<Fp>,
+                                       main_gate: &MainGate<Fp>,
+                                       ctx: &mut RegionCtx<'_, Fp>|
+                          -> Result<(), Error> {
+                        let value = main_gate.assign_value(ctx, Value::known(value))?;
+                        decompose_forged(range_chip, ctx, &value, limb_bit_len, bit_len)
+                    };
+                    assert_eq!(
+                        verify_in::<FixedEnable, _>(K, forged).is_ok(),
+                        verify_in::<Composition<false>, _>(K, forged).is_ok(),
+                        "limb bit length: {limb_bit_len}, bit length: {bit_len}"
+                    );
+                }
+
+                let value = power_of_two::<Fp>(bit_len) - Fp::one();
+                let result = verify_in::<FixedEnable, _>(K, move |range_chip, main_gate, ctx| {
+                    let assigned = range_chip.range_check(ctx, Value::known(value), bit_len)?;
+                    main_gate.assert_equal_to_constant(ctx, &assigned, value)
+                });
+                assert_eq!(result, Ok(()), "bit length: {bit_len}");
             }
-            None
+        }
+    }
+
+    /// Range chip with bitwise operations of 4 bit operands
+    struct Bitwise;
+
+    impl<F: PrimeField> Configure<F> for Bitwise {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config =
+                RangeChip::<F>::configure_with_bitwise(meta, &main_gate_config, vec![4], vec![], 4);
+            TestCircuitConfig { range_config }
+        }
+    }
+
+    #[test]
+    fn test_range_bitwise() {
+        use rand::Rng;
+        use rand_core::OsRng;
+        const BIT_LEN: usize = 4;
+
+        let verify_bitwise = |synthesize: Synthesize<Fp>| verify_in::<Bitwise, _>(K, synthesize);
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let TestCircuitConfig { range_config } = Bitwise::configure(&mut meta);
+        assert_eq!(
+            range_config.table_len(),
+            1 + (1 << BIT_LEN) + (3 << (2 * BIT_LEN))
+        );
+        assert_eq!(
+            range_config.cost(1, BIT_LEN, BIT_LEN).lookup_arguments,
+            meta.lookups().len()
+        );
+
+        let pairs: Vec<(u64, u64)> = [(0, 0), (0xf, 0xf), (0xa, 0x5)]
+            .into_iter()
+            .chain((0..8).map(|_| (OsRng.gen_range(0..16), OsRng.gen_range(0..16))))
+            .collect();
+        let result = verify_bitwise(Box::new(move |range_chip, main_gate, ctx| {
+            for &(a, b) in pairs.iter() {
+                let assigned_a =
+                    range_chip.assign(ctx, Value::known(Fp::from(a)), BIT_LEN, BIT_LEN)?;
+                let assigned_b =
+                    range_chip.assign(ctx, Value::known(Fp::from(b)), BIT_LEN, BIT_LEN)?;
+                let and = range_chip.and(ctx, &assigned_a, &assigned_b)?;
+                let xor = range_chip.xor(ctx, &assigned_a, &assigned_b)?;
+                let or = range_chip.or(ctx, &assigned_a, &assigned_b)?;
+                main_gate.assert_equal_to_constant(ctx, &and, Fp::from(a & b))?;
+                main_gate.assert_equal_to_constant(ctx, &xor, Fp::from(a ^ b))?;
+                main_gate.assert_equal_to_constant(ctx, &or, Fp::from(a | b))?;
+            }
+            Ok(())
+        }));
+        assert_eq!(result, Ok(()));
+
+        // Bitwise row with given cells under the tag of given operation
+        fn forged(values: [Fp; 3], op: BitwiseOp) -> Synthesize<Fp> {
+            Box::new(move |range_chip, main_gate, ctx| {
+                let config = &range_chip.config;
+                let (_, tag) = config.bitwise.unwrap();
+                ctx.enable(config.s_bitwise.unwrap())?;
+                ctx.assign_fixed(
+                    || "tag_bitwise",
+                    config.tag_bitwise.unwrap(),
+                    Fp::from((tag + op as usize) as u64),
+                )?;
+                main_gate.apply(
+                    ctx,
+                    values.map(|value| Term::Unassigned(Value::known(value), Fp::ZERO)),
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+                Ok(())
+            })
+        }
+        let result = verify_bitwise(forged([0xa, 0x6, 0xa & 0x6].map(Fp::from), BitwiseOp::And));
+        assert_eq!(result, Ok(()));
+
+        // Output of `0xa & 0x6` is forged as the output of OR
+        let result = verify_bitwise(forged([0xa, 0x6, 0xa | 0x6].map(Fp::from), BitwiseOp::And));
+        assert!(result.is_err());
+
+        // Output of `0x1 & 0x2` is forged so that the packed value meets the
+        // row of `0x3 & 0x2`, which leaves the output out of range
+        let packed = Fp::from(0x3 + (0x2 << BIT_LEN) + ((0x3 & 0x2) << (2 * BIT_LEN)));
+        let c = (packed - Fp::from(0x1 + (0x2 << BIT_LEN)))
+            * power_of_two::<Fp>(2 * BIT_LEN).invert().unwrap();
+        let result = verify_bitwise(forged([Fp::from(0x1), Fp::from(0x2), c], BitwiseOp::And));
+        assert!(result.is_err());
+    }
+
+    /// Range chip with a dynamic table
+    struct Dynamic;
+
+    impl<F: PrimeField> Configure<F> for Dynamic {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config = RangeChip::<F>::configure_with_dynamic_table(
+                meta,
+                &main_gate_config,
+                vec![8],
+                vec![],
+            );
+            TestCircuitConfig { range_config }
+        }
+    }
+
+    #[test]
+    fn test_range_dynamic_table() {
+        let verify_dynamic = |value: u64| {
+            verify_in::<Dynamic, _>(K, move |range_chip, main_gate, ctx| {
+                // Table rows are witnesses that happen to be squares
+                for i in 1..=16u64 {
+                    let row = main_gate.assign_value(ctx, Value::known(Fp::from(i * i)))?;
+                    range_chip.push_table_row(ctx, &row)?;
+                }
+                let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                range_chip.assert_in_dynamic_table(ctx, &value)?;
+                range_chip.assign(ctx, Value::known(Fp::from(0xffff)), 8, 16)?;
+                Ok(())
+            })
         };
 
-        let (s_overflow, tag_overflow) = if !overflow_bit_lens.is_empty() {
-            let s_overflow = meta.complex_selector();
-            let tag_overflow = if overflow_bit_lens.len() > 1 {
-                let tag = meta.fixed_column();
-                Self::configure_lookup_with_column_tag(
-                    meta,
-                    "overflow_a",
-                    s_overflow,
-                    tag,
-                    a,
-                    t_tag,
-                    t_value,
-                );
-                Some(tag)
+        for (value, expect_ok) in [
+            (1u64, true),
+            (49, true),
+            (256, true),
+            (0, false),
+            (2, false),
+            (289, false),
+        ] {
+            assert_eq!(verify_dynamic(value).is_ok(), expect_ok, "value: {value}");
+        }
+    }
+
+    #[test]
+    fn test_range_narrow_layout() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        let number_of_lookups = |narrow: bool| {
+            let mut meta = ConstraintSystem::<Fp>::default();
+            let main_gate_config = MainGate::<Fp>::configure(&mut meta);
+            let configure = if narrow {
+                RangeChip::<Fp>::configure_narrow
             } else {
-                Self::configure_lookup_with_constant_tag(
-                    meta,
-                    "overflow_a",
-                    s_overflow,
-                    bit_len_tag[&overflow_bit_lens[0]],
-                    a,
-                    t_tag,
-                    t_value,
-                );
-                None
+                RangeChip::<Fp>::configure
             };
+            configure(&mut meta, &main_gate_config, vec![8], vec![3]);
+            meta.lookups().len()
+        };
+        assert_eq!(number_of_lookups(false), NUMBER_OF_LOOKUP_LIMBS + 1);
+        assert_eq!(number_of_lookups(true), 2);
 
-            (Some(s_overflow), tag_overflow)
-        } else {
-            (None, None)
+        // Same values pass or fail in both layouts
+        for bit_len in [1, 8, 27, 64, 70] {
+            for delta in [0u64, 1] {
+                let value = power_of_two::<Fp>(bit_len) - Fp::from(delta);
+                let number_of_limbs = (bit_len - 1) / LIMB_BIT_LEN + 1;
+                let synthesize =
+                    move |range_chip: &RangeChip<Fp>,
+                          main_gate: &MainGate<Fp>,
+                          ctx: &mut RegionCtx<'_, Fp>| {
+                        // Bypass the witness check to reach the constraints
+                        let limbs = decompose(value, number_of_limbs, LIMB_BIT_LEN)
+                            .into_iter()
+                            .map(Value::known)
+                            .collect();
+                        let (assigned, limbs) =
+                            range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, bit_len)?;
+                        assert_eq!(limbs.len(), number_of_limbs);
+                        let expected = main_gate.assign_value(ctx, Value::known(value))?;
+                        main_gate.assert_equal(ctx, &assigned, &expected)
+                    };
+
+                let [wide, narrow] = [
+                    run_with::<false, _>(synthesize),
+                    run_with::<true, _>(synthesize),
+                ]
+                .map(|prover| match prover {
+                    Ok(prover) => prover.verify().is_ok(),
+                    Err(e) => panic!("{:#?}", e),
+                });
+                assert_eq!(wide, delta == 1, "bit length: {bit_len}");
+                assert_eq!(wide, narrow, "bit length: {bit_len}");
+            }
+        }
+
+        let result = run_with::<true, _>(move |range_chip, _, ctx| {
+            let offset = ctx.offset();
+            range_chip.assign(ctx, Value::known(Fp::one()), LIMB_BIT_LEN, 70)?;
+            assert_eq!(ctx.offset() - offset, 9);
+            Ok(())
+        });
+        assert_eq!(result.map(|prover| prover.verify()).unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn test_range_compose() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        // limbs, delta on the composed value, range limbs, expected
+        for (limbs, delta, range_limbs, expect_ok) in [
+            ([0x67u64, 0x45, 0x23, 0x01], 0u64, true, true),
+            ([0x67, 0x45, 0x23, 0x01], 0, false, true),
+            ([0x67, 0x45, 0x23, 0x01], 1, true, false),
+            ([0x67, 0x45, 0x23, 0x01], 1, false, false),
+            ([0x67, 0x145, 0x23, 0x01], 0, true, false),
+            ([0x67, 0x145, 0x23, 0x01], 0, false, true),
+        ] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let assigned = limbs
+                    .iter()
+                    .map(|limb| {
+                        main_gate.assign_to_column(
+                            ctx,
+                            Value::known(Fp::from(*limb)),
+                            MainGateColumn::C,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let composed = range_chip.compose(ctx, &assigned, LIMB_BIT_LEN, range_limbs)?;
+                let expected = limbs
+                    .iter()
+                    .rev()
+                    .fold(0u64, |acc, limb| (acc << LIMB_BIT_LEN) + limb);
+                main_gate.assert_equal_to_constant(ctx, &composed, Fp::from(expected + delta))
+            });
+            assert_eq!(result.is_ok(), expect_ok, "limbs: {limbs:?}");
+        }
+    }
+
+    #[test]
+    fn test_range_decompose_if() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 20;
+
+        let in_range = power_of_two::<Fp>(BIT_LEN) - Fp::one();
+        let out_of_range = power_of_two::<Fp>(BIT_LEN);
+        for (cond, value, expect_ok) in [
+            (1u64, in_range, true),
+            (0, in_range, true),
+            (0, out_of_range, true),
+            (0, -Fp::one(), true),
+            (1, out_of_range, false),
+        ] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let cond = main_gate.assign_bit(ctx, Value::known(Fp::from(cond)))?;
+                    let value = main_gate.assign_value(ctx, Value::known(value))?;
+                    let limbs =
+                        range_chip.decompose_if(ctx, &cond, &value, LIMB_BIT_LEN, BIT_LEN)?;
+                    assert_eq!(limbs.len(), 3);
+                    Ok(())
+                },
+                expect_ok,
+            );
+        }
+
+        // Condition must be a bit
+        let result = verify(|range_chip, main_gate, ctx| {
+            let cond = main_gate.assign_value(ctx, Value::known(Fp::from(2)))?;
+            let value = main_gate.assign_value(ctx, Value::known(Fp::ZERO))?;
+            range_chip.decompose_if(ctx, &cond, &value, LIMB_BIT_LEN, BIT_LEN)?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        // Unset condition doesn't allow arbitrary limbs for the masked value
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let cond = main_gate.assign_bit(ctx, Value::known(Fp::ZERO))?;
+            let value = main_gate.assign_value(ctx, Value::known(out_of_range))?;
+            let masked = main_gate.mul(ctx, &value, &cond)?;
+            let limbs = [0xff, 0xff, 0x0f]
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect();
+            let (decomposed, _) =
+                range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+            ctx.constrain_equal(masked.cell(), decomposed.cell())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_assign_constant_in_range() {
+        const BIT_LEN: usize = 20;
+
+        let max = power_of_two::<Fp>(BIT_LEN) - Fp::one();
+        for (constant, expect_ok) in [(Fp::ZERO, true), (max, true), (max + Fp::one(), false)] {
+            assert_synthesized(
+                move |range_chip, _, ctx| {
+                    let offset = ctx.offset();
+                    range_chip.assign_constant_in_range(ctx, constant, BIT_LEN)?;
+                    // single main gate row without any lookups
+                    assert_eq!(ctx.offset(), offset + 1);
+                    Ok(())
+                },
+                expect_ok,
+            );
+        }
+
+        // Constant is fixed, advice copies must match it
+        for (delta, expect_ok) in [(Fp::ZERO, true), (Fp::one(), false)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let constant = range_chip.assign_constant_in_range(ctx, max, BIT_LEN)?;
+                let value = main_gate.assign_value(ctx, Value::known(max + delta))?;
+                ctx.constrain_equal(constant.cell(), value.cell())
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
+    }
+
+    #[test]
+    fn test_range_decompose_endianness() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        // With and without the overflow limb
+        for bit_len in [32, 36] {
+            let value = Fp::from(0xa_1234_5678 & ((1 << bit_len) - 1));
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let mut decompose = |endianness| {
+                    range_chip.decompose_with_endianness(
+                        ctx,
+                        Value::known(value),
+                        LIMB_BIT_LEN,
+                        bit_len,
+                        endianness,
+                    )
+                };
+                let (_, little) = decompose(Endianness::LittleEndian)?;
+                let (assigned, big) = decompose(Endianness::BigEndian)?;
+
+                // Limbs are mirror images of each other
+                assert_eq!(little.len(), big.len());
+                for (little, big) in little.iter().zip(big.iter().rev()) {
+                    main_gate.assert_equal(ctx, little, big)?;
+                }
+                let terms: Vec<Term<Fp>> = big
+                    .iter()
+                    .rev()
+                    .zip(range_chip.bases(LIMB_BIT_LEN))
+                    .map(|(limb, base)| Term::Assigned(limb, *base))
+                    .collect();
+                let composed = main_gate.compose(ctx, &terms, Fp::ZERO)?;
+                main_gate.assert_equal(ctx, &composed, &assigned)?;
+                main_gate.assert_equal_to_constant(
+                    ctx,
+                    &big[0],
+                    Fp::from(value.to_repr().as_ref()[(bit_len - 1) / LIMB_BIT_LEN] as u64),
+                )
+            });
+            assert_eq!(result, Ok(()), "bit length: {bit_len}");
+        }
+    }
+
+    #[test]
+    fn test_range_to_bytes() {
+        use rand_core::OsRng;
+
+        for number_of_bytes in [1, 3, 4, 17, 31, 32] {
+            let value = if number_of_bytes == 32 {
+                Fp::random(OsRng)
+            } else {
+                power_of_two::<Fp>(8 * number_of_bytes) - Fp::one()
+            };
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let (assigned, bytes) =
+                    range_chip.to_bytes(ctx, Value::known(value), number_of_bytes)?;
+                assert_eq!(bytes.len(), number_of_bytes);
+                let repr = value.to_repr();
+                for (byte, expected) in bytes.iter().zip(repr.as_ref().iter()) {
+                    main_gate.assert_equal_to_constant(ctx, byte, Fp::from(*expected as u64))?;
+                }
+                main_gate.assert_equal_to_constant(ctx, &assigned, value)
+            });
+            assert_eq!(result, Ok(()), "number of bytes: {number_of_bytes}");
+        }
+
+        // `0x0100 = 0xff + 1` where the first byte can't be 0x100
+        let result = verify(|range_chip, main_gate, ctx| {
+            let bytes = [0x100, 0xff]
+                .into_iter()
+                .map(|byte| Value::known(Fp::from(byte)))
+                .collect();
+            let (assigned, _) = range_chip.decompose_unchecked(ctx, bytes, 8, 16)?;
+            main_gate.assert_equal_to_constant(ctx, &assigned, Fp::from(0x10000))
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_min_k() {
+        const LIMB_BIT_LEN: usize = 8;
+        const OVERFLOW_BIT_LEN: usize = 3;
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = TestCircuit::<Fp>::configure(&mut meta);
+        assert_eq!(config.range_config.table_len(), 1 + (1 << 8) + (1 << 3));
+
+        let min_k = config.range_config.min_k(meta.blinding_factors(), 0);
+        assert_eq!(min_k, 9);
+
+        let circuit = TestCircuit::<Fp> {
+            inputs: vec![Input {
+                value: Value::known(Fp::from(1 << 20)),
+                limb_bit_len: LIMB_BIT_LEN,
+                bit_len: 3 * LIMB_BIT_LEN + OVERFLOW_BIT_LEN,
+            }],
         };
+        let prover = MockProver::run(min_k, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
 
-        RangeConfig {
-            main_gate_config: main_gate_config.clone(),
-            bit_len_tag,
-            t_tag,
-            t_value,
-            s_composition,
-            tag_composition,
-            s_overflow,
-            tag_overflow,
+        assert_eq!(
+            TestCircuitLoadTable::load_error(min_k - 1)
+                .unwrap()
+                .to_string(),
+            "range chip table of 265 rows with 8 bit limbs doesn't fit in k = 8, minimum k is 9"
+        );
+
+        // Large number of rows dominates the table
+        assert_eq!(config.range_config.min_k(meta.blinding_factors(), 1000), 10);
+    }
+
+    #[test]
+    fn test_range_number_of_rows() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        fn synthesize(
+            bit_lens: &'static [usize],
+        ) -> impl Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error>
+        {
+            move |range_chip, _, ctx| {
+                let start = ctx.offset();
+                let mut expected = 0;
+                for bit_len in bit_lens.iter().copied() {
+                    let offset = ctx.offset();
+                    range_chip.assign(ctx, Value::known(Fp::one()), LIMB_BIT_LEN, bit_len)?;
+                    let number_of_rows = range_chip.number_of_rows(LIMB_BIT_LEN, bit_len);
+                    assert_eq!(
+                        ctx.offset() - offset,
+                        number_of_rows,
+                        "bit length: {bit_len}"
+                    );
+                    expected += number_of_rows;
+                }
+                // chained decompositions follow each other without gaps
+                assert_eq!(ctx.offset() - start, expected);
+                Ok(())
+            }
+        }
+
+        const BIT_LENS: &[usize] = &[1, 8, 32, 33, 64, 70, 128, 135];
+        for prover in [
+            run_with::<false, _>(synthesize(BIT_LENS)),
+            run_with::<true, _>(synthesize(BIT_LENS)),
+        ] {
+            assert_eq!(prover.unwrap().verify(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_range_decompose_with_overflow() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 64;
+        const OVERFLOW_BIT_LEN: usize = 3;
+
+        for (value, overflow, expect_ok) in [
+            (power_of_two::<Fp>(BIT_LEN) - Fp::one(), 0u64, true),
+            (power_of_two::<Fp>(BIT_LEN), 1, true),
+            (power_of_two::<Fp>(BIT_LEN) + Fp::one(), 1, true),
+            (
+                power_of_two::<Fp>(BIT_LEN + OVERFLOW_BIT_LEN) - Fp::one(),
+                7,
+                true,
+            ),
+            (power_of_two::<Fp>(BIT_LEN + OVERFLOW_BIT_LEN), 0, false),
+        ] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let (assigned, limbs, assigned_overflow) = range_chip.decompose_with_overflow(
+                        ctx,
+                        Value::known(value),
+                        LIMB_BIT_LEN,
+                        BIT_LEN,
+                        OVERFLOW_BIT_LEN,
+                    )?;
+                    assert_eq!(limbs.len(), BIT_LEN / LIMB_BIT_LEN);
+                    main_gate.assert_equal_to_constant(ctx, &assigned, value)?;
+                    main_gate.assert_equal_to_constant(ctx, &assigned_overflow, Fp::from(overflow))
+                },
+                expect_ok,
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_wide_limb_bases() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let main_gate_config = MainGate::<Fp>::configure(&mut meta);
+        let range_config =
+            RangeChip::<Fp>::configure(&mut meta, &main_gate_config, vec![20, 24, 32, 63], vec![]);
+        let range_chip = RangeChip::<Fp>::new(range_config);
+
+        for limb_bit_len in [20, 24, 32, 63] {
+            let bases = range_chip.bases(limb_bit_len);
+            let number_of_limbs = (Fp::NUM_BITS as usize - 1) / limb_bit_len + 1;
+            assert_eq!(bases.len(), number_of_limbs);
+            for (i, base) in bases.iter().enumerate() {
+                assert_eq!(*base, power_of_two::<Fp>(limb_bit_len * i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_assert_small_values() {
+        const BIT_LEN: usize = 8;
+        const NUMBER_OF_VALUES: usize = 6;
+
+        let result = verify(|range_chip, main_gate, ctx| {
+            let values = (0..NUMBER_OF_VALUES)
+                .map(|i| main_gate.assign_value(ctx, Value::known(Fp::from(0xff - i as u64))))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let offset = ctx.offset();
+            range_chip.assert_small_values(ctx, &values, BIT_LEN)?;
+            assert_eq!(
+                ctx.offset() - offset,
+                (NUMBER_OF_VALUES - 1) / NUMBER_OF_LOOKUP_LIMBS + 1
+            );
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+
+        // Each value is individually checked
+        for position in 0..NUMBER_OF_VALUES {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let values = (0..NUMBER_OF_VALUES)
+                    .map(|i| {
+                        let value = if i == position { 0x100 } else { 0xff };
+                        main_gate.assign_value(ctx, Value::known(Fp::from(value)))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                range_chip.assert_small_values(ctx, &values, BIT_LEN)
+            });
+            assert!(result.is_err(), "position: {position}");
+        }
+    }
+
+    #[test]
+    fn test_range_chain_through_values() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 32;
+
+        let result = verify(|range_chip, main_gate, ctx| {
+            let value = Value::known(Fp::from(0x01020304));
+            let (_, limbs) = range_chip.decompose(ctx, value, LIMB_BIT_LEN, BIT_LEN)?;
+
+            // Next witness is computed from returned limb values alone and
+            // stays unknown if any limb is unknown
+            let sum = limbs.iter().fold(Value::known(Fp::ZERO), |acc, limb| {
+                acc + limb.value().copied()
+            });
+            let sum = range_chip.assign(ctx, sum, LIMB_BIT_LEN, LIMB_BIT_LEN)?;
+
+            let terms: Vec<Term<Fp>> = limbs
+                .iter()
+                .map(|limb| Term::Assigned(limb, Fp::one()))
+                .chain(iter::once(Term::Assigned(&sum, -Fp::one())))
+                .collect();
+            main_gate.assert_zero_sum(ctx, &terms, Fp::ZERO)?;
+            main_gate.assert_equal_to_constant(ctx, &sum, Fp::from(10))
+        });
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_range_sub_with_borrow() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 32;
+
+        let max = (1u64 << BIT_LEN) - 1;
+        for (a, b, c, borrow) in [
+            (5u64, 3u64, 2u64, 0u64),
+            (3, 3, 0, 0),
+            (max, 0, max, 0),
+            (3, 5, max - 1, 1),
+            (0, max, 1, 1),
+        ] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let a = range_chip.assign(ctx, Value::known(Fp::from(a)), LIMB_BIT_LEN, BIT_LEN)?;
+                let b = range_chip.assign(ctx, Value::known(Fp::from(b)), LIMB_BIT_LEN, BIT_LEN)?;
+                let (assigned_c, assigned_borrow) =
+                    range_chip.sub_with_borrow(ctx, &a, &b, LIMB_BIT_LEN, BIT_LEN)?;
+                main_gate.assert_equal_to_constant(ctx, &assigned_c, Fp::from(c))?;
+                main_gate.assert_equal_to_constant(ctx, &assigned_borrow, Fp::from(borrow))
+            });
+            assert_eq!(result, Ok(()), "a: {a}, b: {b}");
+        }
+
+        // Try to forge the borrow for `5 - 3`, `c = 2 + 2^32` needs a carry in
+        // the top limb that doesn't fit in a limb
+        for (c, top_limb) in [(2u64, 0u64), (2, 0x100)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let a = main_gate.assign_value(ctx, Value::known(Fp::from(5)))?;
+                let b = main_gate.assign_value(ctx, Value::known(Fp::from(3)))?;
+                let borrow = main_gate.assign_bit(ctx, Value::known(Fp::one()))?;
+                let limbs = [c, 0, 0, top_limb]
+                    .into_iter()
+                    .map(|limb| Value::known(Fp::from(limb)))
+                    .collect();
+                let (c, _) = range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, BIT_LEN)?;
+                main_gate.assert_zero_sum(
+                    ctx,
+                    &[
+                        Term::Assigned(&a, Fp::one()),
+                        Term::Assigned(&b, -Fp::one()),
+                        Term::Assigned(&borrow, power_of_two(BIT_LEN)),
+                        Term::Assigned(&c, -Fp::one()),
+                    ],
+                    Fp::ZERO,
+                )
+            });
+            assert!(result.is_err(), "top limb: {top_limb}");
+        }
+    }
+
+    #[test]
+    fn test_range_assert_canonical() {
+        use halo2wrong::utils::modulus;
+        use num_bigint::BigUint;
+        use num_traits::One;
+
+        for limb_bit_len in [4, 8] {
+            let number_of_limbs = (Fp::NUM_BITS as usize - 1) / limb_bit_len + 1;
+            let bit_len = number_of_limbs * limb_bit_len;
+            let p = modulus::<Fp>();
+            for (value, expect_ok) in [
+                (BigUint::from(0u64), true),
+                (&p - 2usize, true),
+                (&p - 1usize, true),
+                (p.clone(), false),
+                (&p + 1usize, false),
+                ((BigUint::one() << bit_len) - 1usize, false),
+            ] {
+                assert_synthesized(
+                    move |range_chip, _, ctx| {
+                        let limbs = range_chip.decompose_big(
+                            ctx,
+                            Value::known(value.clone()),
+                            limb_bit_len,
+                            bit_len,
+                        )?;
+                        range_chip.assert_canonical(ctx, &limbs, limb_bit_len)
+                    },
+                    expect_ok,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_bounded_by_constant() {
+        use num_bigint::BigUint;
+        use num_traits::One;
+        const LIMB_BIT_LEN: usize = 8;
+
+        // Goldilocks modulus, `bound - 1` is `0xffffffff_00000000`
+        let bound = (BigUint::one() << 64) - (BigUint::one() << 32) + 1usize;
+        for (value, expect_ok) in [
+            (0u64, true),
+            (0xffffffff_00000000, true),
+            (0xfffffffe_ffffffff, true),
+            (0xffffffff_00000001, false),
+            (0xffffffff_00000100, false),
+            (u64::MAX, false),
+        ] {
+            let bound = bound.clone();
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                    range_chip.assert_bounded_by_constant(ctx, &value, LIMB_BIT_LEN, &bound)
+                },
+                expect_ok,
+            );
+        }
+
+        // Bound that isn't aligned to limbs
+        for (value, expect_ok) in [(999u64, true), (1000, false), (1 << 10, false)] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                    range_chip.assert_bounded_by_constant(ctx, &value, 4, &BigUint::from(1000u64))
+                },
+                expect_ok,
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_range_check() {
+        for bit_len in 1..=128 {
+            for (delta, expect_ok) in [(1u64, true), (0, false)] {
+                assert_synthesized(
+                    move |range_chip, main_gate, ctx| {
+                        // 8 bit limbs always work with overflows of 1 to 7 bits
+                        assert_eq!(range_chip.limb_bit_len_for(bit_len), Some(8));
+                        let value = power_of_two::<Fp>(bit_len) - Fp::from(delta);
+                        let offset = ctx.offset();
+                        let assigned = range_chip.range_check(ctx, Value::known(value), bit_len)?;
+                        assert_eq!(ctx.offset() - offset, range_chip.number_of_rows(8, bit_len));
+                        main_gate.assert_equal_to_constant(ctx, &assigned, value)
+                    },
+                    expect_ok,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_single_composition_width() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        // Overflow width divides these bit lengths but it is never a limb size
+        // since composition lookups check against the 8 bit table
+        for bit_len in [3, 6, 9, 12] {
+            let result = run_in::<SingleWidth, _>(K, move |range_chip, _, _| {
+                let expected = (bit_len == 3).then_some(LIMB_BIT_LEN);
+                assert_eq!(range_chip.limb_bit_len_for(bit_len), expected);
+                Ok(())
+            });
+            assert!(result.is_ok());
         }
-    }
 
-    fn configure_lookup_with_column_tag(
-        meta: &mut ConstraintSystem<F>,
-        name: &'static str,
-        selector: Selector,
-        tag: Column<Fixed>,
-        value: Column<Advice>,
-        t_tag: TableColumn,
-        t_value: TableColumn,
-    ) {
-        meta.lookup(name, |meta| {
-            let selector = meta.query_selector(selector);
-            let tag = meta.query_fixed(tag, Rotation::cur());
-            let value = meta.query_advice(value, Rotation::cur());
-            vec![(tag, t_tag), (selector * value, t_value)]
-        });
+        for bit_len in [11, 19] {
+            for (value, expect_ok) in [
+                (power_of_two::<Fp>(bit_len) - Fp::one(), true),
+                (power_of_two::<Fp>(bit_len), false),
+            ] {
+                let result = verify_in::<SingleWidth, _>(K, move |range_chip, main_gate, ctx| {
+                    assert_eq!(range_chip.limb_bit_len_for(bit_len), Some(LIMB_BIT_LEN));
+                    let value = main_gate.assign_value(ctx, Value::known(value))?;
+                    decompose_forged(range_chip, ctx, &value, LIMB_BIT_LEN, bit_len)
+                });
+                assert_eq!(result.is_ok(), expect_ok, "bit length: {bit_len}");
+            }
+        }
     }
 
-    fn configure_lookup_with_constant_tag(
-        meta: &mut ConstraintSystem<F>,
-        name: &'static str,
-        selector: Selector,
-        tag: usize,
-        value: Column<Advice>,
-        t_tag: TableColumn,
-        t_value: TableColumn,
-    ) {
-        meta.lookup(name, |meta| {
-            let selector = meta.query_selector(selector);
-            let tag = selector.clone() * Expression::Constant(F::from(tag as u64));
-            let value = meta.query_advice(value, Rotation::cur());
-            vec![(tag, t_tag), (selector * value, t_value)]
+    #[test]
+    #[should_panic(expected = "composition lookup is not set, bit length: 3")]
+    fn test_range_overflow_width_as_limb() {
+        // A 3 bit limb would only be checked against the 8 bit table
+        let _ = run_in::<SingleWidth, _>(K, |range_chip, main_gate, ctx| {
+            let value = main_gate.assign_value(ctx, Value::known(Fp::from(0x20)))?;
+            decompose_forged(range_chip, ctx, &value, 3, 6)
         });
     }
 
-    fn bases(&self, limb_bit_len: usize) -> &[F] {
-        self.bases
-            .get(&limb_bit_len)
-            .unwrap_or_else(|| panic!("composition table is not set, bit lenght: {}", limb_bit_len))
-            .as_slice()
-    }
-}
+    #[test]
+    fn test_range_constant_and_variable_paths() {
+        const BIT_LEN: usize = 64;
 
-#[cfg(test)]
-mod tests {
+        let value = power_of_two::<Fp>(BIT_LEN) - Fp::one();
+        let result = verify(move |range_chip, main_gate, ctx| {
+            let offset = ctx.offset();
+            let variable = range_chip.range_check(ctx, Value::known(value), BIT_LEN)?;
+            let variable_rows = ctx.offset() - offset;
 
-    use halo2wrong::halo2::arithmetic::Field;
-    use halo2wrong::halo2::circuit::Value;
-    use halo2wrong::RegionCtx;
+            let offset = ctx.offset();
+            let constant = range_chip.assign_constant_in_range(ctx, value, BIT_LEN)?;
+            let constant_rows = ctx.offset() - offset;
 
-    use super::{RangeChip, RangeConfig, RangeInstructions};
-    use crate::curves::{ff::PrimeField, pasta::Fp};
-    use crate::halo2::circuit::{Layouter, SimpleFloorPlanner};
-    use crate::halo2::dev::MockProver;
-    use crate::halo2::plonk::{Circuit, ConstraintSystem, Error};
-    use crate::main_gate::MainGate;
-    use crate::{MainGateInstructions, Term};
+            assert_eq!(variable_rows, range_chip.number_of_rows(8, BIT_LEN));
+            assert_eq!(constant_rows, 1);
+            main_gate.assert_equal(ctx, &variable, &constant)
+        });
+        assert_eq!(result, Ok(()));
+    }
 
-    #[derive(Clone, Debug)]
-    struct TestCircuitConfig {
-        range_config: RangeConfig,
+    #[test]
+    fn test_range_div_rem_pow2() {
+        const LIMB_BIT_LEN: usize = 8;
+        const K: usize = 16;
+        const QUOTIENT_BIT_LEN: usize = 20;
+
+        for (quotient, remainder) in [
+            (0u64, 0u64),
+            (0x12345, 0),
+            (0x12345, 0xffff),
+            ((1 << QUOTIENT_BIT_LEN) - 1, 0xffff),
+        ] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                let value = Fp::from((quotient << K) + remainder);
+                let (assigned, assigned_quotient, assigned_remainder) = range_chip.div_rem_pow2(
+                    ctx,
+                    Value::known(value),
+                    LIMB_BIT_LEN,
+                    K,
+                    QUOTIENT_BIT_LEN,
+                )?;
+                main_gate.assert_equal_to_constant(ctx, &assigned, value)?;
+                main_gate.assert_equal_to_constant(ctx, &assigned_quotient, Fp::from(quotient))?;
+                main_gate.assert_equal_to_constant(ctx, &assigned_remainder, Fp::from(remainder))
+            });
+            assert_eq!(
+                result,
+                Ok(()),
+                "quotient: {quotient}, remainder: {remainder}"
+            );
+        }
+
+        // Forge `q * 2^16 + r` as `(q - 1) * 2^16 + (r + 2^16)` where the
+        // remainder carries into a limb that doesn't fit
+        let result = verify(|range_chip, main_gate, ctx| {
+            let (quotient, remainder) = (0x12345u64, 0x0102u64);
+            let value =
+                main_gate.assign_value(ctx, Value::known(Fp::from((quotient << K) + remainder)))?;
+            let forged_quotient = range_chip.assign(
+                ctx,
+                Value::known(Fp::from(quotient - 1)),
+                LIMB_BIT_LEN,
+                QUOTIENT_BIT_LEN,
+            )?;
+            let limbs = [0x02, 0x01 + 0x100]
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect();
+            let (forged_remainder, _) =
+                range_chip.decompose_unchecked(ctx, limbs, LIMB_BIT_LEN, K)?;
+            main_gate.assert_zero_sum(
+                ctx,
+                &[
+                    Term::Assigned(&forged_remainder, Fp::one()),
+                    Term::Assigned(&forged_quotient, power_of_two(K)),
+                    Term::Assigned(&value, -Fp::one()),
+                ],
+                Fp::ZERO,
+            )
+        });
+        assert!(result.is_err());
     }
 
-    impl TestCircuitConfig {
-        fn new<F: PrimeField>(
-            meta: &mut ConstraintSystem<F>,
-            composition_bit_lens: Vec<usize>,
-            overflow_bit_lens: Vec<usize>,
-        ) -> Self {
-            let main_gate_config = MainGate::<F>::configure(meta);
+    #[test]
+    fn test_range_cost() {
+        const LIMB_BIT_LEN: usize = 8;
+        const BIT_LEN: usize = 70;
+        const NUMBER_OF_DECOMPOSITIONS: usize = 3;
 
-            let range_config = RangeChip::<F>::configure(
-                meta,
-                &main_gate_config,
-                composition_bit_lens,
-                overflow_bit_lens,
+        for narrow in [false, true] {
+            let mut meta = ConstraintSystem::<Fp>::default();
+            let config = if narrow {
+                Composition::<true>::configure(&mut meta)
+            } else {
+                Composition::<false>::configure(&mut meta)
+            };
+            let cost = config
+                .range_config
+                .cost(NUMBER_OF_DECOMPOSITIONS, LIMB_BIT_LEN, BIT_LEN);
+            assert_eq!(cost.lookup_arguments, meta.lookups().len());
+            assert_eq!(
+                cost.lookup_advices,
+                if narrow { 1 } else { NUMBER_OF_LOOKUP_LIMBS }
             );
-            Self { range_config }
+            assert_eq!(cost.table_rows, config.range_config.table_len());
+
+            let synthesize =
+                move |range_chip: &RangeChip<Fp>, _: &MainGate<Fp>, ctx: &mut RegionCtx<'_, Fp>| {
+                    let offset = ctx.offset();
+                    for _ in 0..NUMBER_OF_DECOMPOSITIONS {
+                        range_chip.assign(ctx, Value::known(Fp::one()), LIMB_BIT_LEN, BIT_LEN)?;
+                    }
+                    assert_eq!(ctx.offset() - offset, cost.rows);
+                    Ok(())
+                };
+            let prover = if narrow {
+                run_with::<true, _>(synthesize)
+            } else {
+                run_with::<false, _>(synthesize)
+            };
+            assert_eq!(prover.unwrap().verify(), Ok(()));
         }
+    }
 
-        fn main_gate<F: PrimeField>(&self) -> MainGate<F> {
-            MainGate::<F>::new(self.range_config.main_gate_config.clone())
+    /// Range chip with a membership lookup for values below bounds or for
+    /// values of a set
+    struct Membership<const SET: bool = false>;
+
+    impl<const SET: bool> Membership<SET> {
+        fn bounds() -> Vec<u64> {
+            vec![1000, 3]
         }
 
-        fn range_chip<F: PrimeField>(&self) -> RangeChip<F> {
-            RangeChip::<F>::new(self.range_config.clone())
+        fn set<F: PrimeField>() -> Vec<F> {
+            [5u64, 1, 7, 255, 5, 1].into_iter().map(F::from).collect()
         }
     }
 
-    #[derive(Clone, Debug)]
-    struct Input<F: PrimeField> {
-        bit_len: usize,
-        limb_bit_len: usize,
-        value: Value<F>,
+    impl<F: PrimeField, const SET: bool> Configure<F> for Membership<SET> {
+        type Config = TestCircuitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let main_gate_config = MainGate::<F>::configure(meta);
+            let range_config = if SET {
+                RangeChip::<F>::configure_with_values(
+                    meta,
+                    &main_gate_config,
+                    vec![8],
+                    vec![],
+                    Self::set(),
+                )
+            } else {
+                RangeChip::<F>::configure_with_bounds(
+                    meta,
+                    &main_gate_config,
+                    vec![8],
+                    vec![],
+                    Self::bounds(),
+                )
+            };
+            TestCircuitConfig { range_config }
+        }
     }
 
-    #[derive(Default, Clone, Debug)]
-    struct TestCircuit<F: PrimeField> {
-        inputs: Vec<Input<F>>,
+    fn verify_membership<const SET: bool, S>(synthesize: S) -> Result<(), Vec<VerifyFailure>>
+    where
+        S: Fn(&RangeChip<Fp>, &MainGate<Fp>, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+    {
+        // Table has more than `2^10` rows
+        verify_in::<Membership<SET>, _>(K + 1, synthesize)
     }
 
-    impl<F: PrimeField> TestCircuit<F> {
-        fn composition_bit_lens() -> Vec<usize> {
-            vec![8]
+    #[test]
+    fn test_range_below_bound() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = Membership::<false>::configure(&mut meta);
+        assert_eq!(config.range_config.table_len(), 1 + (1 << 8) + 1000 + 3);
+        assert_eq!(config.range_config.bound_tag(1000), Some(3));
+        assert_eq!(config.range_config.bound_tag(3), Some(2));
+
+        for (bound, value, expect_ok) in [
+            (1000, 0u64, true),
+            (1000, 999, true),
+            (1000, 1000, false),
+            (1000, 1024, false),
+            (3, 2, true),
+            (3, 3, false),
+            (3, 999, false),
+        ] {
+            let result = verify_membership::<false, _>(move |range_chip, main_gate, ctx| {
+                let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                range_chip.assert_below_bound(ctx, &value, bound)?;
+                // Bound and bit length lookups share the table
+                range_chip.assign(ctx, Value::known(Fp::from(0xffff)), 8, 16)?;
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok, "bound: {bound}, value: {value}");
         }
+    }
 
-        fn overflow_bit_lens() -> Vec<usize> {
-            vec![3]
+    #[test]
+    fn test_range_tag_swap() {
+        // Same row with an 8 bit value on one of the lookup columns is looked
+        // up against the 8 and 4 bit tags of the shared table
+        for column in 0..NUMBER_OF_LOOKUP_LIMBS {
+            for (bit_len, expect_ok) in [(8, true), (4, false)] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let config = &range_chip.config;
+                    let tag = config.bit_len_tag(bit_len).unwrap();
+                    config.s_composition.enable(ctx)?;
+                    ctx.assign_fixed(
+                        || "tag_composition",
+                        config.tag_composition.unwrap(),
+                        Fp::from(tag as u64),
+                    )?;
+                    let terms = (0..NUMBER_OF_LOOKUP_LIMBS).map(move |i| {
+                        let value = if i == column { 0xff } else { 0 };
+                        Term::Unassigned(Value::known(Fp::from(value)), Fp::ZERO)
+                    });
+                    main_gate.apply(
+                        ctx,
+                        terms,
+                        Fp::ZERO,
+                        CombinationOptionCommon::OneLinerAdd.into(),
+                    )?;
+                    Ok(())
+                });
+                assert_eq!(
+                    result.is_ok(),
+                    expect_ok,
+                    "column: {column}, bit length: {bit_len}"
+                );
+            }
         }
     }
 
-    impl<F: PrimeField> Circuit<F> for TestCircuit<F> {
-        type Config = TestCircuitConfig;
-        type FloorPlanner = SimpleFloorPlanner;
-        #[cfg(feature = "circuit-params")]
-        type Params = ();
+    #[test]
+    fn test_range_check_k() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = TestCircuit::<Fp>::configure(&mut meta);
+        let blinding_factors = meta.blinding_factors();
+        let min_k = config.range_config.min_k(blinding_factors, 0);
 
-        fn without_witnesses(&self) -> Self {
-            Self::default()
-        }
+        let error = config
+            .range_config
+            .check_k(5, blinding_factors)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            RangeError::TableTooLarge {
+                k: 5,
+                required_k: 9,
+                bit_len: 8,
+                table_len: 1 + (1 << 8) + (1 << 3),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "range chip table of 265 rows with 8 bit limbs doesn't fit in k = 5, minimum k is 9"
+        );
+        assert_eq!(min_k, 9);
+        assert!(matches!(
+            config.range_config.check_k(min_k - 1, blinding_factors),
+            Err(RangeError::TableTooLarge {
+                k: 8,
+                required_k: 9,
+                ..
+            })
+        ));
+        assert!(config.range_config.check_k(min_k, blinding_factors).is_ok());
 
-        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-            TestCircuitConfig::new(
-                meta,
-                Self::composition_bit_lens(),
-                Self::overflow_bit_lens(),
-            )
-        }
+        // Table that doesn't fit fails at the same `k` during synthesis with
+        // the same error
+        assert_eq!(
+            TestCircuitLoadTable::load_error(min_k - 1),
+            config
+                .range_config
+                .check_k(min_k - 1, blinding_factors)
+                .err()
+        );
+        assert_eq!(
+            TestCircuitLoadTable::load_error(5).unwrap().to_string(),
+            error.to_string()
+        );
+        assert_eq!(TestCircuitLoadTable::load_error(min_k), None);
 
-        fn synthesize(
-            &self,
-            config: Self::Config,
-            mut layouter: impl Layouter<F>,
-        ) -> Result<(), Error> {
-            let range_chip = config.range_chip();
-            let main_gate = config.main_gate();
+        // It is still reported to halo2 as too few rows
+        let circuit = TestCircuitLoadTable::default();
+        assert!(matches!(
+            MockProver::<Fp>::run(min_k - 1, &circuit, vec![vec![]]),
+            Err(Error::NotEnoughRowsAvailable { current_k }) if current_k == min_k - 1
+        ));
+    }
 
-            layouter.assign_region(
-                || "region 0",
-                |region| {
-                    let offset = 0;
-                    let ctx = &mut RegionCtx::new(region, offset);
+    #[test]
+    fn test_range_in_set() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = Membership::<true>::configure(&mut meta);
+        // Duplicated values are loaded once
+        assert_eq!(config.range_config.table_len(), 1 + (1 << 8) + 4);
 
-                    for input in self.inputs.iter() {
-                        let value = input.value;
-                        let limb_bit_len = input.limb_bit_len;
-                        let bit_len = input.bit_len;
+        for (value, expect_ok) in [
+            (1u64, true),
+            (5, true),
+            (7, true),
+            (255, true),
+            (0, false),
+            (6, false),
+            (256, false),
+        ] {
+            let result = verify_membership::<true, _>(move |range_chip, main_gate, ctx| {
+                let value = main_gate.assign_value(ctx, Value::known(Fp::from(value)))?;
+                range_chip.assert_in_set(ctx, &value)?;
+                range_chip.assign(ctx, Value::known(Fp::from(0xffff)), 8, 16)?;
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok, "value: {value}");
+        }
+    }
 
-                        let a_0 = main_gate.assign_value(ctx, value)?;
-                        let (a_1, decomposed) =
-                            range_chip.decompose(ctx, value, limb_bit_len, bit_len)?;
+    #[test]
+    fn test_range_disabled_rows_with_set_without_zero() {
+        let set = Membership::<true>::set::<Fp>();
+        assert!(!set.contains(&Fp::ZERO));
 
-                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+        // Rows with arbitrary values on every lookup column and no selector
+        // enabled, next to a membership row
+        let result = verify_membership::<true, _>(|range_chip, main_gate, ctx| {
+            for value in [6u64, 0x1234, 0xffff_ffff] {
+                main_gate.apply(
+                    ctx,
+                    (0..NUMBER_OF_LOOKUP_LIMBS)
+                        .map(|_| Term::Unassigned(Value::known(Fp::from(value)), Fp::ZERO)),
+                    Fp::ZERO,
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?;
+            }
+            let value = main_gate.assign_value(ctx, Value::known(Fp::from(7)))?;
+            range_chip.assert_in_set(ctx, &value)
+        });
+        assert_eq!(result, Ok(()));
+    }
 
-                        let terms: Vec<Term<F>> = decomposed
+    #[test]
+    fn test_range_limbs_from_other_cells() {
+        const LIMB_BIT_LEN: usize = 8;
+
+        // Limbs come from a scratch row of the main gate and are routed
+        // through the range chip with and without a composition
+        for (limbs, expect_ok) in [([0x01u64, 0x02, 0x03], true), ([0x01, 0x102, 0x03], false)] {
+            for compose in [false, true] {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let values = limbs.map(|limb| Value::known(Fp::from(limb)));
+                    let assigned = main_gate.apply(
+                        ctx,
+                        values
                             .iter()
-                            .zip(range_chip.bases(limb_bit_len))
-                            .map(|(limb, base)| Term::Assigned(limb, *base))
-                            .collect();
-                        let a_1 = main_gate.compose(ctx, &terms[..], F::ZERO)?;
-                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+                            .map(|value| Term::Unassigned(*value, Fp::ZERO)),
+                        Fp::ZERO,
+                        CombinationOptionCommon::OneLinerAdd.into(),
+                    )?;
+                    let assigned = &assigned[..limbs.len()];
+                    if compose {
+                        range_chip.compose(ctx, assigned, LIMB_BIT_LEN, true)?;
+                    } else {
+                        range_chip.assert_small_values(ctx, assigned, LIMB_BIT_LEN)?;
                     }
-
                     Ok(())
-                },
-            )?;
+                });
+                assert_eq!(
+                    result.is_ok(),
+                    expect_ok,
+                    "limbs: {limbs:?}, compose: {compose}"
+                );
+            }
+        }
+    }
 
-            range_chip.load_table(&mut layouter)?;
+    #[test]
+    fn test_range_configure_standalone() {
+        use std::collections::BTreeSet;
 
-            Ok(())
-        }
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let range_config = RangeChip::<Fp>::configure_standalone(&mut meta, vec![8], vec![3]);
+        let [a, b, c, d] = range_config.lookup_advices();
+        let lookup_advices = BTreeSet::from_iter([a, b, c, d].map(|column| column.index()));
+        assert_eq!(lookup_advices.len(), NUMBER_OF_LOOKUP_LIMBS);
+        assert_eq!(meta.lookups().len(), NUMBER_OF_LOOKUP_LIMBS + 1);
+
+        // Main gate config with a repeated column is rejected before any
+        // lookup is configured
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let mut main_gate_config = MainGate::<Fp>::configure(&mut meta);
+        main_gate_config.c = main_gate_config.b;
+        let error = RangeChip::<Fp>::try_configure(&mut meta, &main_gate_config, vec![8], vec![3])
+            .unwrap_err();
+        assert_eq!(
+            error,
+            RangeError::DuplicateColumn {
+                index: main_gate_config.b.index()
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "advice column {} is given for more than one lookup",
+                main_gate_config.b.index()
+            )
+        );
+        assert!(meta.lookups().is_empty());
+
+        let main_gate_config = MainGate::<Fp>::configure(&mut meta);
+        assert!(
+            RangeChip::<Fp>::try_configure(&mut meta, &main_gate_config, vec![8], vec![3]).is_ok()
+        );
     }
 
     #[test]
-    fn test_range_circuit() {
+    #[should_panic(expected = "lookup columns are expected to be distinct")]
+    fn test_range_configure_duplicate_column() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let mut main_gate_config = MainGate::<Fp>::configure(&mut meta);
+        main_gate_config.d = main_gate_config.a;
+        RangeChip::<Fp>::configure(&mut meta, &main_gate_config, vec![8], vec![3]);
+    }
+
+    #[test]
+    fn test_range_interleaved_with_main_gate() {
         const LIMB_BIT_LEN: usize = 8;
-        const OVERFLOW_BIT_LEN: usize = 3;
-        let k: u32 = (LIMB_BIT_LEN + 1) as u32;
 
-        let inputs = (2..20)
-            .map(|number_of_limbs| {
-                let bit_len = LIMB_BIT_LEN * number_of_limbs + OVERFLOW_BIT_LEN;
-                let value = Fp::from(2).pow(&[bit_len as u64, 0, 0, 0]) - Fp::one();
-                Input {
-                    value: Value::known(value),
-                    limb_bit_len: LIMB_BIT_LEN,
-                    bit_len,
-                }
-            })
-            .collect();
+        let mut meta = ConstraintSystem::<Fp>::default();
+        RangeChip::<Fp>::configure_standalone(&mut meta, vec![LIMB_BIT_LEN], vec![4]);
+        assert_eq!(meta.num_advice_columns(), 5);
 
-        let circuit = TestCircuit::<Fp> { inputs };
-        let public_inputs = vec![vec![]];
-        let prover = match MockProver::run(k, &circuit, public_inputs) {
-            Ok(prover) => prover,
-            Err(e) => panic!("{:#?}", e),
-        };
-        assert_eq!(prover.verify(), Ok(()));
+        // Products of ranged values are ranged again right after the main
+        // gate row that makes them. `0xff * 0xfff` takes 20 bits
+        for (bit_len, expect_ok) in [(20, true), (16, false)] {
+            assert_synthesized(
+                move |range_chip, main_gate, ctx| {
+                    let a =
+                        range_chip.assign(ctx, Value::known(Fp::from(0xff)), LIMB_BIT_LEN, 8)?;
+                    let b =
+                        range_chip.assign(ctx, Value::known(Fp::from(0xfff)), LIMB_BIT_LEN, 12)?;
+                    let ab = main_gate.mul(ctx, &a, &b)?;
+                    range_chip.decompose_assigned(ctx, &ab, LIMB_BIT_LEN, bit_len)?;
+                    let r = main_gate.mul_add(ctx, &ab, &b, &a)?;
+                    let expected = Fp::from(0xff * 0xfff * 0xfff + 0xff);
+                    main_gate.assert_equal_to_constant(ctx, &r, expected)
+                },
+                expect_ok,
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_planned_rows() {
+        for limb_bit_len in [4, 8] {
+            for bit_len in 1..Fp::NUM_BITS as usize {
+                let result = verify(move |range_chip, main_gate, ctx| {
+                    let value = power_of_two::<Fp>(bit_len) - Fp::one();
+                    let offset = ctx.offset();
+                    let (assigned, limbs) =
+                        range_chip.decompose(ctx, Value::known(value), limb_bit_len, bit_len)?;
+                    // Layout depends only on the bit length
+                    assert_eq!(
+                        ctx.offset() - offset,
+                        range_chip.number_of_rows(limb_bit_len, bit_len)
+                    );
+                    assert_eq!(limbs.len(), (bit_len - 1) / limb_bit_len + 1);
+                    main_gate.assert_equal_to_constant(ctx, &assigned, value)
+                });
+                assert_eq!(
+                    result,
+                    Ok(()),
+                    "limb bit length: {limb_bit_len}, bit length: {bit_len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_mixed_overflow_widths() {
+        // Two leftover widths checked by the same chip in one region
+        for (delta, expect_ok) in [(1u64, true), (0, false)] {
+            let result = verify(move |range_chip, main_gate, ctx| {
+                for bit_len in [8 * 2 + 3, 8 * 2 + 5] {
+                    let value = power_of_two::<Fp>(bit_len) - Fp::from(delta);
+                    let limbs = decompose(value, 3, 8)
+                        .into_iter()
+                        .map(Value::known)
+                        .collect();
+                    let (assigned, _) = range_chip.decompose_unchecked(ctx, limbs, 8, bit_len)?;
+                    main_gate.assert_equal_to_constant(ctx, &assigned, value)?;
+                }
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), expect_ok);
+        }
     }
 }
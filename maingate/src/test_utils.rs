@@ -0,0 +1,113 @@
+//! Test circuit that is shared by unit tests of the chips
+
+use crate::curves::pasta::Fp;
+use crate::halo2::circuit::{Layouter, SimpleFloorPlanner, Value};
+use crate::halo2::dev::{MockProver, VerifyFailure};
+use crate::halo2::halo2curves::ff::PrimeField;
+use crate::halo2::plonk::{Circuit, ConstraintSystem, Error};
+use halo2wrong::RegionCtx;
+use std::rc::Rc;
+
+/// Config of the chips under test
+pub(crate) trait TestConfig<F: PrimeField>: Clone {
+    /// Loads tables after the region is synthesized. Nothing is loaded by
+    /// default
+    fn load(&self, _layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Configure function of [`TestCircuitWith`]
+pub(crate) trait Configure<F: PrimeField> {
+    /// Config that is given to the synthesis function
+    type Config: TestConfig<F>;
+
+    /// Configures the chips under test
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config;
+}
+
+type Synthesize<F, C> =
+    Rc<dyn Fn(&<C as Configure<F>>::Config, &mut RegionCtx<'_, F>, Value<()>) -> Result<(), Error>>;
+
+/// Test circuit that is configured with `C` and runs given synthesis function
+/// in a single region. Tables are loaded afterwards
+pub(crate) struct TestCircuitWith<F: PrimeField, C: Configure<F>> {
+    synthesize: Synthesize<F, C>,
+    witness: Value<()>,
+}
+
+impl<F: PrimeField, C: Configure<F>> TestCircuitWith<F, C> {
+    /// Synthesis function is given a witness that is known unless the circuit
+    /// is without witnesses. Inputs that are mapped from it are unknown at
+    /// key generation as they are in a circuit with its own fields
+    pub(crate) fn new<S>(synthesize: S) -> Self
+    where
+        S: Fn(&C::Config, &mut RegionCtx<'_, F>, Value<()>) -> Result<(), Error> + 'static,
+    {
+        Self {
+            synthesize: Rc::new(synthesize),
+            witness: Value::known(()),
+        }
+    }
+}
+
+impl<F: PrimeField, C: Configure<F>> Circuit<F> for TestCircuitWith<F, C> {
+    type Config = C::Config;
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        // Synthesis function makes the same fixed assignments with or without
+        // witnesses, so it is shared
+        Self {
+            synthesize: self.synthesize.clone(),
+            witness: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        C::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "region 0",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                (self.synthesize)(&config, ctx, self.witness)
+            },
+        )?;
+
+        config.load(&mut layouter)
+    }
+}
+
+/// Runs the test circuit that is configured with `C` in the mock prover.
+/// Synthesis function always has the witness
+pub(crate) fn run<C, S>(k: u32, synthesize: S) -> Result<MockProver<Fp>, Error>
+where
+    C: Configure<Fp>,
+    S: Fn(&C::Config, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+{
+    let circuit = TestCircuitWith::<Fp, C>::new(move |config, ctx, _| synthesize(config, ctx));
+    MockProver::run(k, &circuit, vec![vec![]])
+}
+
+/// Verifies the test circuit that is configured with `C`. Panics if the
+/// witness is rejected at synthesis time
+pub(crate) fn verify<C, S>(k: u32, synthesize: S) -> Result<(), Vec<VerifyFailure>>
+where
+    C: Configure<Fp>,
+    S: Fn(&C::Config, &mut RegionCtx<'_, Fp>) -> Result<(), Error> + 'static,
+{
+    match run::<C, _>(k, synthesize) {
+        Ok(prover) => prover.verify(),
+        Err(e) => panic!("{:#?}", e),
+    }
+}
@@ -0,0 +1,275 @@
+use maingate::halo2::circuit::{Layouter, SimpleFloorPlanner, Value};
+use maingate::halo2::dev::MockProver;
+use maingate::halo2::halo2curves::{ff::PrimeField, pasta::Fp};
+use maingate::halo2::plonk::{Circuit, ConstraintSystem, Error, Expression, Selector};
+use maingate::halo2::poly::Rotation;
+use maingate::{
+    AssignedValue, MainGate, MainGateColumn, MainGateConfig, MainGateInstructions, RangeChip,
+    RangeConfig, RangeInstructions, RegionCtx,
+};
+
+const LIMB_BIT_LEN: usize = 8;
+const OVERFLOW_BIT_LEN: usize = 4;
+
+/// A gadget that only knows about the range instruction set
+fn assign_all<F: PrimeField, R: RangeInstructions<F>>(
+    range_chip: &R,
+    ctx: &mut RegionCtx<'_, F>,
+    values: &[Value<F>],
+    bit_len: usize,
+) -> Result<Vec<AssignedValue<F>>, Error> {
+    values
+        .iter()
+        .map(|value| range_chip.assign(ctx, *value, LIMB_BIT_LEN, bit_len))
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct TestCircuitConfig {
+    main_gate_config: MainGateConfig,
+    range_config: RangeConfig,
+}
+
+#[derive(Default, Clone, Debug)]
+struct TestCircuit<F: PrimeField> {
+    values: Vec<Value<F>>,
+    bit_len: usize,
+}
+
+impl<F: PrimeField> Circuit<F> for TestCircuit<F> {
+    type Config = TestCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            values: vec![Value::unknown(); self.values.len()],
+            bit_len: self.bit_len,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let main_gate_config = MainGate::<F>::configure(meta);
+        let range_config = RangeChip::<F>::configure(
+            meta,
+            &main_gate_config,
+            vec![LIMB_BIT_LEN],
+            vec![OVERFLOW_BIT_LEN],
+        );
+        TestCircuitConfig {
+            main_gate_config,
+            range_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let main_gate = MainGate::<F>::new(config.main_gate_config);
+        let range_chip = RangeChip::<F>::new(config.range_config);
+
+        layouter.assign_region(
+            || "region 0",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                let assigned = assign_all(&range_chip, ctx, &self.values, self.bit_len)?;
+                for (assigned, value) in assigned.iter().zip(self.values.iter()) {
+                    let expected = main_gate.assign_value(ctx, *value)?;
+                    main_gate.assert_equal(ctx, assigned, &expected)?;
+                }
+                Ok(())
+            },
+        )?;
+
+        range_chip.load_table(&mut layouter)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_public_api() {
+    const K: u32 = 10;
+    let bit_len = 3 * LIMB_BIT_LEN + OVERFLOW_BIT_LEN;
+
+    let values = [0u64, 1, 1 << 20, (1 << bit_len) - 1]
+        .into_iter()
+        .map(|value| Value::known(Fp::from(value)))
+        .collect();
+    let circuit = TestCircuit::<Fp> { values, bit_len };
+    let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    // Out of range witness is rejected before proving
+    let values = vec![Value::known(Fp::from(1 << bit_len))];
+    let circuit = TestCircuit::<Fp> { values, bit_len };
+    let result = MockProver::run(K, &circuit, vec![vec![]]);
+    assert!(matches!(result, Err(Error::Synthesis)));
+}
+
+#[derive(Clone, Debug)]
+struct TestCircuitSharedColumnsConfig {
+    range_config: RangeConfig,
+    s_custom: Selector,
+}
+
+/// Circuit that adds its own lookup over the range chip columns and table
+#[derive(Default, Clone, Debug)]
+struct TestCircuitSharedColumns<F: PrimeField> {
+    value: Value<F>,
+}
+
+impl<F: PrimeField> Circuit<F> for TestCircuitSharedColumns<F> {
+    type Config = TestCircuitSharedColumnsConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let main_gate_config = MainGate::<F>::configure(meta);
+        let range_config = RangeChip::<F>::configure(
+            meta,
+            &main_gate_config,
+            vec![LIMB_BIT_LEN],
+            vec![OVERFLOW_BIT_LEN],
+        );
+
+        let [_, _, _, d] = range_config.lookup_advices();
+        let (t_tag, t_value) = range_config.table();
+        let tag = range_config.bit_len_tag(LIMB_BIT_LEN).unwrap();
+        let s_custom = meta.complex_selector();
+        meta.lookup("custom", |meta| {
+            let selector = meta.query_selector(s_custom);
+            let value = meta.query_advice(d, Rotation::cur());
+            let tag = selector.clone() * Expression::Constant(F::from(tag as u64));
+            vec![(tag, t_tag), (selector * value, t_value)]
+        });
+
+        TestCircuitSharedColumnsConfig {
+            range_config,
+            s_custom,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let main_gate = MainGate::<F>::new(config.range_config.main_gate_config().clone());
+        let range_chip = RangeChip::<F>::new(config.range_config.clone());
+
+        layouter.assign_region(
+            || "region 0",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                ctx.enable(config.s_custom)?;
+                main_gate.assign_to_column(ctx, self.value, MainGateColumn::D)?;
+                Ok(())
+            },
+        )?;
+
+        range_chip.load_table(&mut layouter)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_config_accessors() {
+    const K: u32 = 10;
+
+    for (value, expect_ok) in [(255u64, true), (256u64, false)] {
+        let circuit = TestCircuitSharedColumns::<Fp> {
+            value: Value::known(Fp::from(value)),
+        };
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify().is_ok(), expect_ok);
+    }
+}
+
+/// Circuit that assigns given limbs without deriving them from the value
+#[derive(Default, Clone, Debug)]
+struct TestCircuitUnchecked<F: PrimeField> {
+    limbs: Vec<Value<F>>,
+    value: Value<F>,
+    bit_len: usize,
+}
+
+impl<F: PrimeField> Circuit<F> for TestCircuitUnchecked<F> {
+    type Config = TestCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            limbs: vec![Value::unknown(); self.limbs.len()],
+            value: Value::unknown(),
+            bit_len: self.bit_len,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        TestCircuit::<F>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let main_gate = MainGate::<F>::new(config.main_gate_config);
+        let range_chip = RangeChip::<F>::new(config.range_config);
+
+        layouter.assign_region(
+            || "region 0",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+                let (assigned, _) = range_chip.decompose_unchecked(
+                    ctx,
+                    self.limbs.clone(),
+                    LIMB_BIT_LEN,
+                    self.bit_len,
+                )?;
+                let expected = main_gate.assign_value(ctx, self.value)?;
+                main_gate.assert_equal(ctx, &assigned, &expected)
+            },
+        )?;
+
+        range_chip.load_table(&mut layouter)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_decompose_unchecked() {
+    const K: u32 = 10;
+    let bit_len = 2 * LIMB_BIT_LEN + OVERFLOW_BIT_LEN;
+
+    // 0x0f_01_00 with canonical limbs and with the middle limb carried into
+    // the first one, which doesn't fit in a limb
+    for (limbs, expect_ok) in [([0x00u64, 0x01, 0x0f], true), ([0x100, 0x00, 0x0f], false)] {
+        let circuit = TestCircuitUnchecked::<Fp> {
+            limbs: limbs
+                .into_iter()
+                .map(|limb| Value::known(Fp::from(limb)))
+                .collect(),
+            value: Value::known(Fp::from(0x0f0100)),
+            bit_len,
+        };
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify().is_ok(), expect_ok, "limbs: {limbs:?}");
+    }
+}